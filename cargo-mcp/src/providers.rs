@@ -1,7 +1,6 @@
 use crate::Config;
 
 pub mod crates_io;
-mod link;
 pub mod metadata;
 pub mod rustdoc;
 
@@ -1,5 +1,8 @@
 use crates_index::{Crate, IndexConfig, Names, SparseIndex, Version};
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
@@ -53,6 +56,16 @@ impl CratesIoProvider {
         name: &str,
         version: Option<&str>,
     ) -> Result<Vec<String>, Error> {
+        let features = self.fetch_feature_map(name, version).await?;
+        Ok(features.into_keys().collect())
+    }
+
+    /// Returns each feature along with the features and dependencies it enables
+    pub async fn fetch_feature_map(
+        &self,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<BTreeMap<String, Vec<String>>, Error> {
         let krate = self.fetch(name).await?;
 
         let version_info = if let Some(version) = version {
@@ -65,7 +78,11 @@ impl CratesIoProvider {
             krate.highest_normal_version().ok_or("No versions found")?
         };
 
-        Ok(version_info.features().keys().cloned().collect())
+        Ok(version_info
+            .features()
+            .iter()
+            .map(|(feature, enables)| (feature.clone(), enables.clone()))
+            .collect())
     }
 
     pub async fn get_download_url(
@@ -121,3 +138,32 @@ impl CratesIoProvider {
         Ok(self.0.index.parse_cache_response(name, response, true)?)
     }
 }
+
+/// Returns the features that transitively enable `feature`, not including itself
+pub fn enabling_features(features: &BTreeMap<String, Vec<String>>, feature: &str) -> Vec<String> {
+    features
+        .keys()
+        .filter(|candidate| *candidate != feature)
+        .filter(|candidate| {
+            let mut stack = vec![candidate.as_str()];
+            let mut seen = BTreeSet::new();
+            while let Some(current) = stack.pop() {
+                if current == feature {
+                    return true;
+                }
+                if !seen.insert(current) {
+                    continue;
+                }
+                // only plain values refer to other features, e.g. not `dep:foo` or `foo/bar`
+                let enables = features.get(current).into_iter().flatten();
+                stack.extend(
+                    enables
+                        .map(String::as_str)
+                        .filter(|value| !value.contains([':', '/'])),
+                );
+            }
+            false
+        })
+        .cloned()
+        .collect()
+}
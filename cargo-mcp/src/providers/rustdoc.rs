@@ -1,14 +1,69 @@
-use rustdoc_types::{Id, ItemEnum, ItemKind};
+use crate::types::{
+    ApiChange, ArgDoc, ChildDoc, ChildGroup, Cursor, Deprecation, Features, FieldDoc, ItemDoc,
+    MethodDoc, ModuleDoc, SearchFilter, SourceDoc, VariantDoc, Visibility,
+};
+use cargo_metadata::{DependencyKind, MetadataCommand, Package, TargetKind};
+use cfg::Cfg;
+use reexports::Reexports;
+use rustdoc_json::PackageTarget;
+use rustdoc_types::{Id, ItemEnum, ItemKind, StructKind, VariantKind};
 use serde::Serialize;
+use signature::Render;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops,
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
+use text_search::DocIndex;
+use type_search::{FnSig, TypeIndex};
+
+pub use api_diff::Bump;
+pub use constructors::{Constructor, ConstructorKind};
+pub use conversions::Conversion;
+pub use impls::{ImplKind, TraitImpl};
+pub use methods::{Method, MethodSet};
+pub use skeleton::ImplSkeleton;
+
+mod api_diff;
+mod attrs;
+mod cfg;
+mod constructors;
+mod conversions;
+mod impls;
+mod methods;
+mod reexports;
+mod signature;
+mod skeleton;
+mod text_search;
+mod type_search;
 
 pub const NIGHTLY_VERSION: &str = "nightly-2025-07-16";
 
+/// Fuzzy path matches below this score aren't included in search results
+const MIN_FUZZY_SCORE: f64 = 0.7;
+
+/// How many levels of crates to follow when resolving re-exports from dependencies
+const MAX_REEXPORT_DEPTH: usize = 2;
+
+/// The order that the children of a module are listed in, with any other kinds last
+const MODULE_KIND_ORDER: &[ItemKind] = &[
+    ItemKind::Module,
+    ItemKind::Macro,
+    ItemKind::ProcAttribute,
+    ItemKind::ProcDerive,
+    ItemKind::Trait,
+    ItemKind::TraitAlias,
+    ItemKind::Struct,
+    ItemKind::Enum,
+    ItemKind::Union,
+    ItemKind::TypeAlias,
+    ItemKind::Function,
+    ItemKind::Constant,
+    ItemKind::Static,
+    ItemKind::Primitive,
+];
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 
@@ -18,14 +73,53 @@ type Str = Arc<str>;
 pub struct Item {
     #[serde(skip)]
     pub id: Id,
+    /// Index into [`Crate::sources`] for the rustdoc output that defines this item
+    #[serde(skip)]
+    pub source: usize,
     #[serde(skip)] // just return the full path
     pub name: Str,
+    /// The shortest public path of the item
     pub path: Str,
+    /// Other public paths the item is re-exported at
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<Str>,
     #[serde(skip)] // this is just used for easier searching
     pub search: Str,
     pub kind: ItemKind,
     #[serde(skip)]
     pub docs: Option<Str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Str>,
+    /// Whether a trait item must be implemented or has a default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trait_item: Option<TraitItem>,
+    /// The header of the inherent impl block that defines the item, e.g.
+    /// `impl<T: Serialize> Wrapper<T>`, which carries the bounds needed to use it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impl_signature: Option<Str>,
+    /// Bounds on an associated type
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bounds: Vec<Str>,
+    /// The value of an associated type or const, or its default inside of a trait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Str>,
+    /// The `cfg` predicate that gates the item, including those of its parents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<Cfg>,
+    /// Features that must be enabled to use the item
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires_features: Vec<Str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    /// Attributes that affect how the item can be used, e.g. `must_use` or `non_exhaustive`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Str>,
+    /// Whether the item is reachable from outside of the crate
+    #[serde(skip)]
+    pub is_public: bool,
+    /// Where the item is defined
+    #[serde(skip)]
+    pub span: Option<Span>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub functions: Vec<Str>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -38,46 +132,121 @@ pub struct Item {
     pub structs: Vec<Str>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub enums: Vec<Str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assoc_types: Vec<Str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assoc_consts: Vec<Str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraitItem {
+    Required,
+    Provided,
 }
 
 impl Item {
-    fn new(id: Id, name: Str, path: Str, search: Str, kind: ItemKind, docs: Option<Str>) -> Self {
+    fn new(name: Str, path: Str, search: Str, kind: ItemKind, info: &rustdoc_types::Item) -> Self {
+        let (bounds, value) = match &info.inner {
+            ItemEnum::AssocType { bounds, type_, .. } => (
+                bounds.iter().map(|b| b.to_source().into()).collect(),
+                type_.as_ref().map(|ty| ty.to_source().into()),
+            ),
+            ItemEnum::AssocConst { value, .. } => (vec![], value.as_deref().map(Str::from)),
+            _ => (vec![], None),
+        };
+
         Self {
-            id,
+            id: info.id,
+            source: 0,
             name,
             path,
+            aliases: vec![],
             search,
             kind,
-            docs,
+            docs: info.docs.clone().map(Str::from),
+            signature: signature::item(info).map(Str::from),
+            trait_item: None,
+            impl_signature: None,
+            bounds,
+            value,
+            cfg: Cfg::from_attrs(&info.attrs),
+            requires_features: vec![],
+            deprecated: attrs::deprecation(info),
+            attributes: attrs::notable(info),
+            is_public: false,
+            span: None,
             functions: vec![],
             variants: vec![],
             traits: vec![],
             trait_impls: vec![],
             structs: vec![],
             enums: vec![],
+            assoc_types: vec![],
+            assoc_consts: vec![],
         }
     }
 
     fn from_summary(
-        id: Id,
         summary: &rustdoc_types::ItemSummary,
         item: &rustdoc_types::Item,
+        public_paths: Option<&Vec<String>>,
         krate: &Crate,
     ) -> Self {
-        let path = Str::from(summary.path.join("::"));
-        let search = path
-            .trim_start_matches(&*krate.name)
-            .trim_start_matches("::")
-            .to_string()
-            .into();
+        let defined_at = summary.path.join("::");
 
+        // Prefer the shortest public path over where the item happens to be defined
+        let (path, aliases) = match public_paths.map(|paths| paths.split_first()) {
+            Some(Some((path, aliases))) => (path.clone(), aliases),
+            _ => (defined_at, &[][..]),
+        };
+
+        let search = krate.search_path(&path).into();
         let kind = summary.kind;
+        let name = summary.path.last().unwrap().clone().into();
 
-        let docs = item.docs.clone().map(Str::from);
+        let is_public = public_paths.is_some() || item.id == krate.root_id;
 
-        let name = summary.path.last().unwrap().clone().into();
+        let mut item = Self::new(name, path.into(), search, kind, item);
+        item.aliases = aliases.iter().map(|alias| alias.as_str().into()).collect();
+        item.is_public = is_public;
+        item
+    }
+
+    /// Returns whether the item passes the `filter`
+    ///
+    /// Deprecated items pass, since [`SearchResults`] counts them before leaving them out.
+    fn matches(&self, filter: &SearchFilter, krate: &Crate) -> bool {
+        if !filter.kinds.is_empty() && !filter.kinds.contains(&self.kind) {
+            return false;
+        }
 
-        Self::new(id, name, path, search, kind, docs)
+        match filter.visibility {
+            Some(Visibility::Public) if !self.is_public => return false,
+            Some(Visibility::Private) if self.is_public => return false,
+            _ => {}
+        }
+
+        if let Some(scope) = &filter.scope {
+            let scope = krate.search_path(scope.trim_end_matches("::"));
+            let in_scope = self.paths().any(|path| {
+                let path = krate.search_path(path);
+                scope.is_empty()
+                    || path
+                        .strip_prefix(scope)
+                        .is_some_and(|rest| rest.starts_with("::"))
+            });
+            if !in_scope {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns every path the item can be referred to by
+    pub fn paths(&self) -> impl Iterator<Item = &Str> {
+        core::iter::once(&self.path).chain(&self.aliases)
     }
 
     fn sort(&mut self) {
@@ -88,6 +257,8 @@ impl Item {
             &mut self.trait_impls,
             &mut self.structs,
             &mut self.enums,
+            &mut self.assoc_types,
+            &mut self.assoc_consts,
         ];
 
         for list in lists {
@@ -114,50 +285,14 @@ impl Item {
     ) {
         match &info.inner {
             ItemEnum::Struct(s) => {
-                // Process impl blocks for structs
-                for &impl_id in &s.impls {
-                    let Some(impl_info) = krate.index.get(&impl_id) else {
-                        continue;
-                    };
-
-                    // Process items in the impl block
-                    if let ItemEnum::Impl(impl_) = &impl_info.inner {
-                        if let Some(i) = impl_.trait_.as_ref() {
-                            self.push_trait_impl(i);
-                            continue;
-                        }
-
-                        for &item_id in &impl_.items {
-                            let Some(info) = krate.index.get(&item_id) else {
-                                continue;
-                            };
-                            let Some(item_name) = info.name.as_ref() else {
-                                continue;
-                            };
-
-                            self.functions.push(item_name.clone().into());
-
-                            let (path, search) = self.child_path(item_name);
-                            let kind = match &info.inner {
-                                ItemEnum::Function(_) => ItemKind::Function,
-                                other => {
-                                    eprint!("unhandled struct impl item {other:?}");
-                                    continue;
-                                }
-                            };
-
-                            let fn_item = Item::new(
-                                item_id,
-                                item_name.clone().into(),
-                                path,
-                                search,
-                                kind,
-                                info.docs.clone().map(Str::from),
-                            );
-                            additional_items.insert(item_id, fn_item);
-                        }
-                    }
+                if let StructKind::Plain { fields, .. } = &s.kind {
+                    self.resolve_fields(fields, krate, additional_items);
                 }
+                self.resolve_impls(&s.impls, krate, additional_items);
+            }
+            ItemEnum::Union(u) => {
+                self.resolve_fields(&u.fields, krate, additional_items);
+                self.resolve_impls(&u.impls, krate, additional_items);
             }
             ItemEnum::Enum(e) => {
                 // Process enum variants
@@ -174,60 +309,65 @@ impl Item {
                     let (path, search) = self.child_path(variant_name);
                     let kind = ItemKind::Variant;
 
-                    let variant_item = Item::new(
-                        *variant,
+                    let mut variant_item = Item::new(
                         variant_name.clone().into(),
                         path,
                         search,
                         kind,
-                        variant_info.docs.clone().map(Str::from),
+                        variant_info,
                     );
+                    variant_item.cfg = Cfg::and(self.cfg.clone(), variant_item.cfg);
+                    variant_item.deprecated = variant_item.deprecated.or(self.deprecated.clone());
+                    variant_item.is_public = self.is_public;
+                    if let ItemEnum::Variant(rustdoc_types::Variant {
+                        kind: VariantKind::Struct { fields, .. },
+                        ..
+                    }) = &variant_info.inner
+                    {
+                        variant_item.resolve_fields(fields, krate, additional_items);
+                    }
                     additional_items.insert(*variant, variant_item);
                 }
 
-                // Process impl blocks for enums
-                for &impl_id in &e.impls {
-                    let Some(impl_info) = krate.index.get(&impl_id) else {
+                self.resolve_impls(&e.impls, krate, additional_items);
+            }
+            ItemEnum::Trait(t) => {
+                for &item_id in &t.items {
+                    let Some(info) = krate.index.get(&item_id) else {
+                        continue;
+                    };
+                    let Some(item_name) = info.name.as_ref() else {
                         continue;
                     };
 
-                    // Process items in the impl block
-                    if let ItemEnum::Impl(impl_) = &impl_info.inner {
-                        if let Some(i) = impl_.trait_.as_ref() {
-                            self.push_trait_impl(i);
-                            continue;
-                        }
-
-                        for &item_id in &impl_.items {
-                            let Some(info) = krate.index.get(&item_id) else {
-                                continue;
-                            };
-                            let Some(item_name) = info.name.as_ref() else {
-                                continue;
-                            };
-
+                    let (kind, provided) = match &info.inner {
+                        ItemEnum::Function(f) => {
                             self.functions.push(item_name.clone().into());
-
-                            let (path, search) = self.child_path(item_name);
-                            let kind = match &info.inner {
-                                ItemEnum::Function(_) => ItemKind::Function,
-                                other => {
-                                    eprint!("unhandled enum impl item {other:?}");
-                                    continue;
-                                }
-                            };
-
-                            let fn_item = Item::new(
-                                item_id,
-                                item_name.clone().into(),
-                                path,
-                                search,
-                                kind,
-                                info.docs.clone().map(Str::from),
-                            );
-                            additional_items.insert(item_id, fn_item);
+                            (ItemKind::Function, f.has_body)
                         }
-                    }
+                        ItemEnum::AssocType { type_, .. } => {
+                            self.assoc_types.push(item_name.clone().into());
+                            (ItemKind::AssocType, type_.is_some())
+                        }
+                        ItemEnum::AssocConst { value, .. } => {
+                            self.assoc_consts.push(item_name.clone().into());
+                            (ItemKind::AssocConst, value.is_some())
+                        }
+                        _ => continue,
+                    };
+
+                    let (path, search) = self.child_path(item_name);
+                    let mut trait_item =
+                        Item::new(item_name.clone().into(), path, search, kind, info);
+                    trait_item.trait_item = Some(if provided {
+                        TraitItem::Provided
+                    } else {
+                        TraitItem::Required
+                    });
+                    trait_item.cfg = Cfg::and(self.cfg.clone(), trait_item.cfg);
+                    trait_item.deprecated = trait_item.deprecated.or(self.deprecated.clone());
+                    trait_item.is_public = self.is_public;
+                    additional_items.insert(item_id, trait_item);
                 }
             }
             _ => {
@@ -236,65 +376,168 @@ impl Item {
         }
     }
 
-    fn push_trait_impl(&mut self, path: &rustdoc_types::Path) {
-        let name = path.path.to_string();
-        if let Some(args) = &path.args {
-            use rustdoc_types::GenericArgs;
+    /// Adds the named fields of a struct, union or variant so they can be searched
+    fn resolve_fields(
+        &self,
+        fields: &[Id],
+        krate: &rustdoc_types::Crate,
+        additional_items: &mut HashMap<Id, Item>,
+    ) {
+        for field in fields {
+            let Some(info) = krate.index.get(field) else {
+                continue;
+            };
+            let Some(name) = info.name.as_ref() else {
+                continue;
+            };
 
-            match &**args {
-                GenericArgs::AngleBracketed { args, constraints } => {
-                    // TODO
-                    let _ = args;
-                    let _ = constraints;
-                }
-                GenericArgs::Parenthesized { inputs, output } => {
-                    // TODO
-                    let _ = inputs;
-                    let _ = output;
-                }
-                GenericArgs::ReturnTypeNotation => {
-                    // TODO
-                }
+            let (path, search) = self.child_path(name);
+            let mut field_item = Item::new(
+                name.clone().into(),
+                path,
+                search,
+                ItemKind::StructField,
+                info,
+            );
+            field_item.cfg = Cfg::and(self.cfg.clone(), field_item.cfg);
+            field_item.deprecated = field_item.deprecated.or(self.deprecated.clone());
+            // the fields of variants are public without being marked `pub`
+            field_item.is_public = self.is_public
+                && matches!(
+                    info.visibility,
+                    rustdoc_types::Visibility::Public | rustdoc_types::Visibility::Default
+                );
+            additional_items.insert(*field, field_item);
+        }
+    }
+
+    fn resolve_impls(
+        &mut self,
+        impls: &[Id],
+        krate: &rustdoc_types::Crate,
+        additional_items: &mut HashMap<Id, Item>,
+    ) {
+        for &impl_id in impls {
+            let Some(impl_info) = krate.index.get(&impl_id) else {
+                continue;
+            };
+
+            // Process items in the impl block
+            let ItemEnum::Impl(impl_) = &impl_info.inner else {
+                continue;
+            };
+
+            if impl_.trait_.is_some() {
+                self.push_trait_impl(impl_, krate);
+                continue;
+            }
+
+            let impl_cfg = Cfg::and(self.cfg.clone(), Cfg::from_attrs(&impl_info.attrs));
+            let impl_deprecated = attrs::deprecation(impl_info).or(self.deprecated.clone());
+            let impl_signature: Option<Str> = signature::item(impl_info).map(Str::from);
+
+            for &item_id in &impl_.items {
+                let Some(info) = krate.index.get(&item_id) else {
+                    continue;
+                };
+                let Some(item_name) = info.name.as_ref() else {
+                    continue;
+                };
+
+                let kind = match &info.inner {
+                    ItemEnum::Function(_) => {
+                        self.functions.push(item_name.clone().into());
+                        ItemKind::Function
+                    }
+                    ItemEnum::AssocConst { .. } => {
+                        self.assoc_consts.push(item_name.clone().into());
+                        ItemKind::AssocConst
+                    }
+                    ItemEnum::AssocType { .. } => {
+                        self.assoc_types.push(item_name.clone().into());
+                        ItemKind::AssocType
+                    }
+                    _ => continue,
+                };
+
+                let (path, search) = self.child_path(item_name);
+                let mut impl_item = Item::new(item_name.clone().into(), path, search, kind, info);
+                impl_item.cfg = Cfg::and(impl_cfg.clone(), impl_item.cfg);
+                impl_item.deprecated = impl_item.deprecated.or(impl_deprecated.clone());
+                impl_item.impl_signature = impl_signature.clone();
+                impl_item.is_public =
+                    self.is_public && matches!(info.visibility, rustdoc_types::Visibility::Public);
+                additional_items.insert(item_id, impl_item);
             }
         }
-        self.trait_impls.push(name.into());
+    }
+
+    fn push_trait_impl(&mut self, impl_: &rustdoc_types::Impl, krate: &rustdoc_types::Crate) {
+        if let Some(name) = signature::trait_impl(impl_, krate) {
+            self.trait_impls.push(name.into());
+        }
     }
 }
 
+/// The location of an item in its source file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub file: PathBuf,
+    /// The first and last lines of the item, starting at 1
+    pub lines: (usize, usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct Crate {
     pub root_id: Id,
     pub name: Str,
+    /// Items keyed by their ID. Items re-exported from other crates are keyed by an offset ID.
     pub items: HashMap<Id, Item>,
     pub paths: HashMap<String, Vec<Id>>, // Maps fully qualified paths to item IDs
+    /// The raw rustdoc output for this crate followed by any crates it re-exports items from
+    pub sources: Vec<Arc<rustdoc_types::Crate>>,
+    /// The directory that the spans of each of the [`Crate::sources`] are relative to
+    pub roots: Vec<Arc<Path>>,
+    /// Trait impls, including those of the crates that items are re-exported from
+    pub impls: Vec<TraitImpl>,
+    /// Function signatures used to search by type
+    pub type_index: TypeIndex,
+    /// Item names and documentation used for full-text search
+    pub doc_index: DocIndex,
+    /// Problems that left the documentation incomplete, e.g. a re-exported crate that couldn't
+    /// be documented
+    pub warnings: Vec<String>,
 }
 
 impl Crate {
-    fn from_crate(krate: &rustdoc_types::Crate, crate_name: Option<&str>) -> Self {
-        let (crate_id, crate_name) = match crate_name {
-            Some(name) => {
-                let id = *krate
-                    .external_crates
-                    .iter()
-                    .find(|(_id, info)| info.name == name)
-                    .unwrap()
-                    .0;
+    /// Processes the rustdoc output for a crate
+    ///
+    /// `dir` is the directory containing the crate's manifest. `dependency` is called for each
+    /// crate that items are re-exported from and should return the processed documentation for
+    /// it, if available.
+    fn from_crate(
+        krate: rustdoc_types::Crate,
+        dir: &Path,
+        dependency: &mut dyn FnMut(&str) -> Option<Crate>,
+    ) -> Self {
+        let krate = Arc::new(krate);
+        let root = krate.index.get(&krate.root).unwrap();
+        let crate_id = root.crate_id;
+        let crate_name = root.name.clone().unwrap();
 
-                (id, name.to_string())
-            }
-            None => {
-                let item = krate.index.get(&krate.root).unwrap();
-                let id = item.crate_id;
-                let name = item.name.clone().unwrap();
-                (id, name)
-            }
-        };
+        let reexports = Reexports::new(&krate, &crate_name);
 
         let mut processed = Self {
             root_id: krate.root,
             name: crate_name.into(),
             items: HashMap::new(),
             paths: HashMap::new(),
+            sources: vec![krate.clone()],
+            roots: vec![span_root(&krate, dir)],
+            impls: impls::collect(&krate),
+            type_index: TypeIndex::default(),
+            doc_index: DocIndex::default(),
+            warnings: vec![],
         };
 
         // First pass: Create all items
@@ -307,73 +550,988 @@ impl Crate {
                 continue;
             }
 
-            let item = Item::from_summary(id, item, &info, &processed);
+            // Keep the defining path searchable, even if it isn't public
+            processed
+                .paths
+                .entry(item.path.join("::"))
+                .or_default()
+                .push(id);
+
+            let item = Item::from_summary(item, info, reexports.local.get(&id), &processed);
 
             processed.items.insert(id, item);
         }
 
+        // Items inherit the `cfg` of the modules they're defined in
+        let module_cfgs: HashMap<&[String], Cfg> = krate
+            .paths
+            .iter()
+            .filter(|(_, summary)| summary.kind == ItemKind::Module)
+            .filter_map(|(id, summary)| {
+                let cfg = Cfg::from_attrs(&krate.index.get(id)?.attrs)?;
+                Some((&summary.path[..], cfg))
+            })
+            .collect();
+        for (id, item) in &mut processed.items {
+            let Some(summary) = krate.paths.get(id) else {
+                continue;
+            };
+            for len in 1..summary.path.len() {
+                if let Some(cfg) = module_cfgs.get(&summary.path[..len]) {
+                    item.cfg = Cfg::and(Some(cfg.clone()), item.cfg.take());
+                }
+            }
+        }
+
         // Build children relationships
         let mut additional_items = HashMap::new();
         for (&id, item) in &mut processed.items {
             let info = krate.index.get(&id).unwrap();
-            item.resolve_children(info, krate, &mut additional_items);
+            item.resolve_children(info, &krate, &mut additional_items);
             item.sort();
         }
         processed.items.extend(additional_items);
 
+        let root = processed.roots[0].clone();
+        for (id, item) in &mut processed.items {
+            if let Some(cfg) = &item.cfg {
+                item.requires_features =
+                    cfg.required_features().into_iter().map(Str::from).collect();
+            }
+            item.span = krate
+                .index
+                .get(id)
+                .and_then(|info| info.span.as_ref())
+                .map(|span| resolve_span(&root, span));
+        }
+
+        // Pull in items that are re-exported from other crates
+        let mut external: Vec<&reexports::External> = reexports.external.iter().collect();
+        external.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+        for group in external.chunk_by(|a, b| a.crate_name == b.crate_name) {
+            if let Some(dependency) = dependency(&group[0].crate_name) {
+                processed.import(&dependency, group);
+            }
+        }
+
+        for (&id, item) in &processed.items {
+            for path in item.paths() {
+                processed
+                    .paths
+                    .entry(path.to_string())
+                    .or_default()
+                    .push(id);
+            }
+        }
+
+        // Prefer types and functions over macros that share the same path (e.g. derives)
+        for ids in processed.paths.values_mut() {
+            ids.sort_by_key(|id| {
+                let kind = processed.items[id].kind;
+                let is_macro = matches!(
+                    kind,
+                    ItemKind::Macro | ItemKind::ProcAttribute | ItemKind::ProcDerive
+                );
+                (is_macro, *id)
+            });
+            ids.dedup();
+        }
+
+        processed.index_types();
+        processed.index_docs();
+
         processed
     }
 
-    pub fn search(&self, query: &str, max_results: Option<usize>) -> Vec<SearchResult<'_>> {
-        let mut exact_matches = Vec::new();
-        let mut scored_matches = Vec::new();
-        let max_results = max_results.unwrap_or(5);
+    /// Records the name and documentation of every item for full-text search
+    fn index_docs(&mut self) {
+        let mut doc_index = DocIndex::default();
 
-        let query = query
-            .trim_start_matches(&*self.name)
-            .trim_start_matches("::");
+        // index in a consistent order so scores don't depend on hash map iteration
+        let mut ids: Vec<_> = self.items.keys().copied().collect();
+        ids.sort();
 
-        for item in self.items.values() {
-            let mut score = strsim::jaro_winkler(&item.search, query);
+        for id in ids {
+            let item = &self.items[&id];
+            doc_index.push(id, &item.name, item.docs.as_deref());
+        }
+
+        self.doc_index = doc_index;
+    }
 
-            // if it's not a perfect match just try the item name instead
-            if score < 1.0 {
-                score = score.max(strsim::jaro_winkler(&item.name, query));
+    /// Records the signature of every function for searching by type
+    fn index_types(&mut self) {
+        let mut type_index = TypeIndex::default();
+
+        for (&id, item) in &self.items {
+            if item.kind != ItemKind::Function {
+                continue;
             }
 
-            let res = SearchResult { score, item };
+            let Some(info) = self.raw(item).index.get(&item.id) else {
+                continue;
+            };
+            let ItemEnum::Function(function) = &info.inner else {
+                continue;
+            };
+
+            // `Self` in a method refers to the type (or trait) it's defined on
+            let self_type = item.path.rsplit_once("::").and_then(|(parent, _)| {
+                let parent = self.paths.get(parent)?.first()?;
+                let parent = &self.items[parent];
+                matches!(
+                    parent.kind,
+                    ItemKind::Struct
+                        | ItemKind::Enum
+                        | ItemKind::Union
+                        | ItemKind::Trait
+                        | ItemKind::Primitive
+                )
+                .then_some(&*parent.name)
+            });
+
+            let sig = FnSig::new(&function.sig, &function.generics, self_type);
+            type_index.push(id, sig);
+        }
+
+        self.type_index = type_index;
+    }
 
-            if res.score == 1.0 {
-                exact_matches.push(res);
-                scored_matches.clear();
+    /// Imports the items from `dependency` that are re-exported by this crate
+    fn import(&mut self, dependency: &Crate, reexports: &[&reexports::External]) {
+        // Match on both the defining path and the canonical path in the dependency so children
+        // of the re-exported item (e.g. methods) follow along
+        let mut rules = vec![];
+        for reexport in reexports {
+            rules.push((reexport.source.clone(), reexport));
+            if let Some(item) = dependency.get(&reexport.source)
+                && *item.path != reexport.source
+            {
+                rules.push((item.path.to_string(), reexport));
+            }
+        }
+
+        let key_offset = self.items.keys().map(|id| id.0 + 1).max().unwrap_or(0);
+        let source_offset = self.sources.len();
+        self.sources.extend(dependency.sources.iter().cloned());
+        self.roots.extend(dependency.roots.iter().cloned());
+        self.warnings.extend(dependency.warnings.iter().cloned());
+
+        // the paths impls refer to the imported items by
+        let mut reachable = HashSet::new();
+        for (id, item) in &dependency.items {
+            let mut paths = vec![];
+            for path in item.paths() {
+                for (source, reexport) in &rules {
+                    if !reexport.is_glob && **path == **source {
+                        paths.push(reexport.alias.clone());
+                    } else if let Some(rest) = path
+                        .strip_prefix(source.as_str())
+                        .and_then(|rest| rest.strip_prefix("::"))
+                    {
+                        paths.push(format!("{}::{rest}", reexport.alias));
+                    }
+                }
+            }
+
+            reexports::sort_paths(&mut paths, &item.name);
+            let mut paths = paths.into_iter();
+            let Some(path) = paths.next() else {
                 continue;
+            };
+            reachable.extend(dependency.defining_path(item));
+
+            let mut item = item.clone();
+            item.search = self.search_path(&path).into();
+            item.path = path.into();
+            item.aliases = paths.map(Str::from).collect();
+            item.source += source_offset;
+            self.items.insert(Id(id.0 + key_offset), item);
+        }
+
+        // impls that involve neither a re-exported type nor trait can't be used from this crate
+        self.impls.extend(
+            dependency
+                .impls
+                .iter()
+                .filter(|impl_| {
+                    reachable.contains(&*impl_.trait_path)
+                        || impl_
+                            .for_path
+                            .as_deref()
+                            .is_some_and(|path| reachable.contains(path))
+                })
+                .cloned(),
+        );
+    }
+
+    /// Returns the path of an item relative to the crate root, which is used for searching
+    fn search_path<'a>(&self, path: &'a str) -> &'a str {
+        path.trim_start_matches(&*self.name)
+            .trim_start_matches("::")
+    }
+
+    /// Returns the rustdoc output that defines `item`
+    pub fn raw(&self, item: &Item) -> &rustdoc_types::Crate {
+        &self.sources[item.source]
+    }
+
+    /// Looks up an item by its path, with or without the leading crate name
+    pub fn get(&self, path: &str) -> Option<&Item> {
+        let path = path.trim_start_matches("::");
+        let ids = match self.paths.get(path) {
+            Some(ids) => ids,
+            None => self.paths.get(&format!("{}::{path}", self.name))?,
+        };
+        ids.iter().find_map(|id| self.items.get(id))
+    }
+
+    /// Returns the full documentation for an item, including its fields, variants and methods
+    pub fn item_doc(&self, item: &Item) -> ItemDoc {
+        let mut doc = ItemDoc {
+            name: item.name.to_string(),
+            path: item.path.to_string(),
+            kind: item.kind,
+            docs: item.docs.as_deref().map(String::from),
+            signature: item.signature.as_deref().map(String::from),
+            impl_signature: item.impl_signature.as_deref().map(String::from),
+            cfg: item.cfg.as_ref().map(|cfg| cfg.to_string()),
+            requires_features: item
+                .requires_features
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            deprecated: item.deprecated.clone(),
+            attributes: item.attributes.iter().map(|a| a.to_string()).collect(),
+            location: item
+                .span
+                .as_ref()
+                .map(|span| format!("{}:{}-{}", span.file.display(), span.lines.0, span.lines.1)),
+            implemented_traits: item.trait_impls.iter().map(|t| t.to_string()).collect(),
+            methods: vec![],
+            fields: vec![],
+            variants: vec![],
+        };
+
+        let raw = self.raw(item);
+        let Some(info) = raw.index.get(&item.id) else {
+            return doc;
+        };
+
+        match &info.inner {
+            ItemEnum::Struct(s) => {
+                doc.fields = match &s.kind {
+                    StructKind::Unit => vec![],
+                    StructKind::Tuple(fields) => field_docs(raw, fields.iter().copied()),
+                    StructKind::Plain { fields, .. } => {
+                        field_docs(raw, fields.iter().copied().map(Some))
+                    }
+                };
+                doc.methods = inherent_method_docs(raw, &s.impls);
+            }
+            ItemEnum::Union(u) => {
+                doc.fields = field_docs(raw, u.fields.iter().copied().map(Some));
+                doc.methods = inherent_method_docs(raw, &u.impls);
             }
+            ItemEnum::Enum(e) => {
+                doc.variants = e
+                    .variants
+                    .iter()
+                    .filter_map(|id| variant_doc(raw, id))
+                    .collect();
+                doc.methods = inherent_method_docs(raw, &e.impls);
+            }
+            ItemEnum::Trait(t) => {
+                doc.methods = method_docs(raw, &t.items);
+            }
+            ItemEnum::Primitive(p) => {
+                doc.methods = inherent_method_docs(raw, &p.impls);
+            }
+            _ => {}
+        }
+
+        // members of a deprecated type are deprecated along with it
+        if let Some(deprecated) = &doc.deprecated {
+            for method in &mut doc.methods {
+                method.deprecated.get_or_insert_with(|| deprecated.clone());
+            }
+        }
+
+        doc
+    }
+
+    /// Returns the source code of an item
+    ///
+    /// If `include_impls` is set, the source of each impl block of a type follows.
+    pub fn item_source(&self, item: &Item, include_impls: bool) -> Result<Vec<SourceDoc>> {
+        let span = item
+            .span
+            .as_ref()
+            .ok_or_else(|| format!("The source of {} isn't available", item.path))?;
+        let mut sources = vec![source_doc(span)?];
+
+        if !include_impls {
+            return Ok(sources);
+        }
 
-            if !exact_matches.is_empty() {
+        let raw = self.raw(item);
+        let impls = match raw.index.get(&item.id).map(|info| &info.inner) {
+            Some(ItemEnum::Struct(s)) => &s.impls[..],
+            Some(ItemEnum::Union(u)) => &u.impls[..],
+            Some(ItemEnum::Enum(e)) => &e.impls[..],
+            _ => &[],
+        };
+
+        for impl_id in impls {
+            let Some(impl_info) = raw.index.get(impl_id) else {
+                continue;
+            };
+            let ItemEnum::Impl(impl_) = &impl_info.inner else {
+                continue;
+            };
+            if impl_.is_synthetic || impl_.blanket_impl.is_some() {
                 continue;
             }
+            let Some(span) = &impl_info.span else {
+                continue;
+            };
 
-            if res.score > 0.2 {
-                scored_matches.push(res);
+            // impls from macros can point at files that weren't vendored, so skip those
+            if let Ok(source) = source_doc(&resolve_span(&self.roots[item.source], span)) {
+                sources.push(source);
             }
         }
 
-        // Return exact matches if we found any
-        if !exact_matches.is_empty() {
-            return exact_matches;
+        Ok(sources)
+    }
+
+    /// Returns whether the source files of the crate and the crates it re-exports from exist
+    fn has_sources(&self) -> bool {
+        self.sources
+            .iter()
+            .zip(&self.roots)
+            .all(|(krate, root)| has_sources(krate, root))
+    }
+
+    /// The version of the crate, if known
+    pub fn version(&self) -> Option<&str> {
+        self.sources[0].crate_version.as_deref()
+    }
+
+    /// Returns the changes to the public API from this version of the crate to `new`
+    pub fn api_diff(&self, new: &Crate) -> Vec<ApiChange> {
+        api_diff::diff(self, new)
+    }
+
+    /// Returns the impls of a trait, which can be defined in this crate or another one
+    ///
+    /// Traits from other crates are matched by path, e.g. `std::error::Error`, or by name.
+    pub fn implementors(&self, trait_path: &str) -> Vec<Implementor<'_>> {
+        let defined_at = self
+            .get(trait_path)
+            .filter(|item| item.kind == ItemKind::Trait)
+            .and_then(|item| self.defining_path(item));
+
+        let mut implementors: Vec<_> = self
+            .impls
+            .iter()
+            .filter(|impl_| match &defined_at {
+                Some(path) => *impl_.trait_path == **path,
+                None => impls::path_matches(&impl_.trait_path, trait_path),
+            })
+            .map(|impl_| Implementor {
+                path: impl_
+                    .for_path
+                    .as_deref()
+                    .and_then(|path| self.get(path))
+                    .map(|item| &item.path),
+                impl_,
+            })
+            .collect();
+
+        implementors.sort_by(|a, b| {
+            (a.impl_.kind, &a.impl_.for_type, &a.impl_.trait_).cmp(&(
+                b.impl_.kind,
+                &b.impl_.for_type,
+                &b.impl_.trait_,
+            ))
+        });
+        implementors.dedup_by(|a, b| a.impl_.signature == b.impl_.signature);
+        implementors
+    }
+
+    /// Returns the traits implemented by a type, including auto traits and blanket impls
+    pub fn implemented_traits(&self, item: &Item) -> Vec<&TraitImpl> {
+        let Some(defined_at) = self.defining_path(item) else {
+            return vec![];
+        };
+
+        let mut impls: Vec<_> = self
+            .impls
+            .iter()
+            .filter(|impl_| impl_.for_path.as_deref() == Some(&*defined_at))
+            .collect();
+
+        impls.sort_by(|a, b| (a.kind, &a.trait_).cmp(&(b.kind, &b.trait_)));
+        impls.dedup_by(|a, b| a.signature == b.signature);
+        impls
+    }
+
+    /// Returns the conversions to and from a type, e.g. `From` and `AsRef` impls
+    ///
+    /// The type can be given as a path or a type expression, e.g. `http::HeaderValue` or `&str`.
+    /// Returns the conversions that produce the type followed by those that consume it.
+    pub fn conversions(&self, ty: &str) -> (Vec<Conversion>, Vec<Conversion>) {
+        conversions::collect(&self.impls)
+            .into_iter()
+            .filter(|conversion| {
+                conversions::type_matches(&conversion.to, ty)
+                    || conversions::type_matches(&conversion.from, ty)
+            })
+            .partition(|conversion| conversions::type_matches(&conversion.to, ty))
+    }
+
+    /// Finds the shortest chain of conversions from one type to another
+    pub fn conversion_path(&self, from: &str, to: &str) -> Option<Vec<Conversion>> {
+        let conversions = conversions::collect(&self.impls);
+        let path = conversions::shortest_path(&conversions, from, to)?;
+        Some(path.into_iter().cloned().collect())
+    }
+
+    /// Returns the methods that can be called on a type, including those reached through
+    /// `Deref`
+    pub fn methods(&self, ty: &str) -> MethodSet {
+        methods::resolve(self, ty)
+    }
+
+    /// Returns the ways to obtain a value of a type, simplest first
+    pub fn constructors(&self, item: &Item) -> Vec<Constructor> {
+        constructors::find(self, item)
+    }
+
+    /// Generates an impl block of a trait for `for_type` with `todo!()` bodies for the required
+    /// items
+    pub fn impl_skeleton(&self, item: &Item, for_type: &str) -> Result<ImplSkeleton> {
+        let raw = self.raw(item);
+        let skeleton = raw
+            .index
+            .get(&item.id)
+            .and_then(|info| skeleton::generate(raw, info, &item.path, for_type))
+            .ok_or_else(|| format!("{} is not a trait", item.path))?;
+        Ok(skeleton)
+    }
+
+    /// Returns the path that an item is defined at, which impls refer to it by
+    fn defining_path(&self, item: &Item) -> Option<String> {
+        let summary = self.raw(item).paths.get(&item.id)?;
+        Some(summary.path.join("::"))
+    }
+
+    /// Returns the direct children of a module grouped by kind, including re-exported items
+    pub fn module_doc(&self, module: &Item) -> ModuleDoc {
+        let is_child = |path: &str| {
+            path.rsplit_once("::")
+                .is_some_and(|(parent, _)| module.paths().any(|p| **p == *parent))
+        };
+
+        // list items under the path they have in this module, which may be a renamed re-export
+        let mut children: Vec<(ItemKind, ChildDoc)> = self
+            .items
+            .values()
+            .filter_map(|item| {
+                let path = item.paths().find(|path| is_child(path))?;
+                let name = path.rsplit_once("::").map_or(&**path, |(_, name)| name);
+                let child = ChildDoc {
+                    name: name.to_string(),
+                    path: path.to_string(),
+                    summary: item.docs.as_deref().and_then(summary),
+                };
+                Some((item.kind, child))
+            })
+            .collect();
+
+        let kind_order = |kind: &ItemKind| {
+            MODULE_KIND_ORDER
+                .iter()
+                .position(|k| k == kind)
+                .unwrap_or(MODULE_KIND_ORDER.len())
+        };
+        children.sort_by(|(a_kind, a), (b_kind, b)| {
+            kind_order(a_kind)
+                .cmp(&kind_order(b_kind))
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        let mut doc = ModuleDoc {
+            path: module.path.to_string(),
+            summary: module.docs.as_deref().and_then(summary),
+            modules: vec![],
+            items: vec![],
+        };
+        for group in children.chunk_by(|(a, _), (b, _)| a == b) {
+            let kind = group[0].0;
+            let items = group.iter().map(|(_, child)| child.clone()).collect();
+            if kind == ItemKind::Module {
+                doc.modules = items;
+            } else {
+                doc.items.push(ChildGroup { kind, items });
+            }
         }
 
-        // Sort fuzzy matches by score, return max_results
-        scored_matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        scored_matches.truncate(max_results);
-        scored_matches
+        doc
+    }
+
+    /// Searches item names and documentation for the terms in `query`
+    ///
+    /// Results include a snippet of the documentation that matched.
+    pub fn search_docs(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        cursor: Cursor,
+        max_results: Option<usize>,
+    ) -> SearchResults<'_> {
+        let results = self
+            .doc_index
+            .search(query, |id| self.items[&id].matches(filter, self))
+            .into_iter()
+            .map(|(id, score)| SearchResult::new(&self.items[&id], score))
+            .collect();
+
+        let mut results = SearchResults::new(results, filter, cursor, max_results);
+        for result in &mut results.results {
+            result.snippet = result
+                .docs
+                .as_deref()
+                .and_then(|docs| text_search::snippet(docs, query));
+        }
+        results
+    }
+
+    /// Searches for functions by their parameter and return types, e.g. `&str -> Result<Url>`
+    pub fn search_signature(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        cursor: Cursor,
+        max_results: Option<usize>,
+    ) -> Result<SearchResults<'_>> {
+        let query = type_search::Query::parse(query).ok_or_else(|| {
+            format!(
+                "Invalid signature query {query:?}. Expected parameter and return types, e.g. `&str, usize -> Vec<u8>`"
+            )
+        })?;
+
+        let results = self
+            .type_index
+            .search(&query, |id| self.items[&id].matches(filter, self))
+            .map(|(id, score)| SearchResult::new(&self.items[&id], score))
+            .collect();
+
+        Ok(SearchResults::new(results, filter, cursor, max_results))
+    }
+
+    pub fn search(&self, query: &str, max_results: Option<usize>) -> SearchResults<'_> {
+        self.search_filtered(
+            query,
+            &SearchFilter::default(),
+            Cursor::default(),
+            max_results,
+        )
+    }
+
+    /// Matches item paths and names, only considering the items that pass `filter`
+    ///
+    /// Exact matches are ranked first, followed by prefix, path suffix and then fuzzy matches.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        cursor: Cursor,
+        max_results: Option<usize>,
+    ) -> SearchResults<'_> {
+        let query = self.search_path(query);
+        let lower_query = query.to_lowercase();
+        let suffix = format!("::{lower_query}");
+
+        let mut results = vec![];
+
+        for item in self.items.values() {
+            if !item.matches(filter, self) {
+                continue;
+            }
+
+            let search_paths = || {
+                core::iter::once(&*item.search)
+                    .chain(item.aliases.iter().map(|alias| self.search_path(alias)))
+            };
+
+            let mut score = search_paths()
+                .chain([&*item.name])
+                .map(|path| strsim::jaro_winkler(path, query))
+                .fold(0.0, f64::max);
+
+            let matched = if score == 1.0 {
+                Match::Exact
+            } else if !query.is_empty()
+                && search_paths()
+                    .chain([&*item.name])
+                    .any(|path| path.to_lowercase().starts_with(&lower_query))
+            {
+                Match::Prefix
+            } else if !query.is_empty()
+                && search_paths().any(|path| path.to_lowercase().ends_with(&suffix))
+            {
+                Match::Suffix
+            } else if score >= MIN_FUZZY_SCORE {
+                Match::Fuzzy
+            } else {
+                continue;
+            };
+
+            // only exact matches should show a perfect score
+            if matched != Match::Exact {
+                score = score.min(0.99);
+            }
+
+            let mut result = SearchResult::new(item, score);
+            result.matched = Some(matched);
+            results.push(result);
+        }
+
+        SearchResults::new(results, filter, cursor, max_results)
+    }
+}
+
+/// Finds the directory that rustdoc was run from, which the spans in its output are relative to
+///
+/// This is the workspace root, which may be any of the ancestors of the crate's directory.
+fn span_root(krate: &rustdoc_types::Crate, dir: &Path) -> Arc<Path> {
+    let root_span = krate
+        .index
+        .get(&krate.root)
+        .and_then(|root| root.span.as_ref());
+    let root = root_span
+        .and_then(|span| {
+            dir.ancestors()
+                .find(|ancestor| ancestor.join(&span.filename).exists())
+        })
+        .unwrap_or(dir);
+    Arc::from(root)
+}
+
+fn resolve_span(root: &Path, span: &rustdoc_types::Span) -> Span {
+    Span {
+        file: root.join(&span.filename),
+        lines: (span.begin.0, span.end.0),
+    }
+}
+
+/// Returns whether the files that the spans of `krate` point at exist under `root`
+///
+/// Files outside of `root`, such as those of the standard library, are ignored.
+fn has_sources(krate: &rustdoc_types::Crate, root: &Path) -> bool {
+    let files: HashSet<&Path> = krate
+        .index
+        .values()
+        .filter_map(|info| info.span.as_ref())
+        .map(|span| span.filename.as_path())
+        .filter(|file| {
+            file.components()
+                .all(|component| matches!(component, std::path::Component::Normal(_)))
+        })
+        .collect();
+    files.into_iter().all(|file| root.join(file).exists())
+}
+
+/// Reads the lines covered by `span`
+fn source_doc(span: &Span) -> Result<SourceDoc> {
+    let contents = std::fs::read_to_string(&span.file)
+        .map_err(|e| format!("Failed to read {}: {e}", span.file.display()))?;
+
+    let (start, end) = span.lines;
+    let source = contents
+        .lines()
+        .skip(start.saturating_sub(1))
+        .take((end + 1).saturating_sub(start.max(1)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(SourceDoc {
+        file: span.file.display().to_string(),
+        start_line: start,
+        end_line: end,
+        source,
+    })
+}
+
+/// Returns the first paragraph of the documentation on a single line
+fn summary(docs: &str) -> Option<String> {
+    let summary = docs
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!summary.is_empty()).then_some(summary)
+}
+
+fn field_docs(
+    raw: &rustdoc_types::Crate,
+    fields: impl Iterator<Item = Option<Id>>,
+) -> Vec<FieldDoc> {
+    fields
+        .enumerate()
+        .filter_map(|(idx, id)| {
+            let info = raw.index.get(&id?)?;
+            let ItemEnum::StructField(ty) = &info.inner else {
+                return None;
+            };
+            Some(FieldDoc {
+                name: info.name.clone().unwrap_or_else(|| idx.to_string()),
+                type_name: ty.to_source(),
+                signature: signature::item(info),
+                docs: info.docs.clone(),
+            })
+        })
+        .collect()
+}
+
+fn variant_doc(raw: &rustdoc_types::Crate, id: &Id) -> Option<VariantDoc> {
+    let info = raw.index.get(id)?;
+    let ItemEnum::Variant(variant) = &info.inner else {
+        return None;
+    };
+    let fields = match &variant.kind {
+        VariantKind::Plain => vec![],
+        VariantKind::Tuple(fields) => field_docs(raw, fields.iter().copied()),
+        VariantKind::Struct { fields, .. } => field_docs(raw, fields.iter().copied().map(Some)),
+    };
+    Some(VariantDoc {
+        name: info.name.clone()?,
+        docs: info.docs.clone(),
+        fields,
+    })
+}
+
+fn inherent_method_docs(raw: &rustdoc_types::Crate, impls: &[Id]) -> Vec<MethodDoc> {
+    let mut methods = vec![];
+    for impl_id in impls {
+        let Some(impl_info) = raw.index.get(impl_id) else {
+            continue;
+        };
+        let ItemEnum::Impl(impl_) = &impl_info.inner else {
+            continue;
+        };
+        if impl_.trait_.is_some() {
+            continue;
+        }
+        let impl_signature = signature::item(impl_info);
+        let impl_deprecated = attrs::deprecation(impl_info);
+        methods.extend(
+            method_docs(raw, &impl_.items)
+                .into_iter()
+                .map(|mut method| {
+                    method.impl_signature = impl_signature.clone();
+                    method.deprecated = method.deprecated.or(impl_deprecated.clone());
+                    method
+                }),
+        );
     }
+    methods
 }
 
-#[derive(Clone, Copy, Debug, Serialize)]
+fn method_docs(raw: &rustdoc_types::Crate, items: &[Id]) -> Vec<MethodDoc> {
+    items
+        .iter()
+        .filter_map(|id| {
+            let info = raw.index.get(id)?;
+            let ItemEnum::Function(function) = &info.inner else {
+                return None;
+            };
+            let args = function
+                .sig
+                .inputs
+                .iter()
+                .map(|(name, ty)| ArgDoc {
+                    name: name.clone(),
+                    type_name: ty.to_source(),
+                    docs: None,
+                })
+                .collect();
+            let return_type = match &function.sig.output {
+                Some(ty) => ty.to_source(),
+                None => "()".to_string(),
+            };
+            Some(MethodDoc {
+                name: info.name.clone()?,
+                signature: signature::item(info),
+                docs: info.docs.clone(),
+                deprecated: attrs::deprecation(info),
+                impl_signature: None,
+                args,
+                return_type,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct SearchResult<'a> {
     pub score: f64,
     pub item: &'a Item,
+    /// How the query matched the item's path, for path searches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched: Option<Match>,
+    /// The part of the item's documentation that matched a full-text search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl<'a> SearchResult<'a> {
+    fn new(item: &'a Item, score: f64) -> Self {
+        Self {
+            score,
+            item,
+            matched: None,
+            snippet: None,
+        }
+    }
+
+    /// The path of the type or module that contains the item
+    pub fn parent(&self) -> &str {
+        self.path.rsplit_once("::").map_or("", |(parent, _)| parent)
+    }
+}
+
+/// A type that implements a trait
+#[derive(Clone, Debug, Serialize)]
+pub struct Implementor<'a> {
+    /// The public path of the implementing type, if it's part of the crate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<&'a Str>,
+    #[serde(flatten)]
+    pub impl_: &'a TraitImpl,
+}
+
+/// The kinds of path matches, from best to worst
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Match {
+    Exact,
+    Prefix,
+    /// The query matches the end of the path, e.g. `Sender::send` for `sync::Sender::send`
+    Suffix,
+    Fuzzy,
+}
+
+/// The top search results, ordered so that results with the same parent are next to each other
+#[derive(Clone, Debug)]
+pub struct SearchResults<'a> {
+    pub results: Vec<SearchResult<'a>>,
+    /// The number of matches after this page of results
+    pub omitted: usize,
+    /// The number of deprecated matches that were left out, since the filter excludes them
+    pub omitted_deprecated: usize,
+    /// The position of the next page of results, if there are any
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<'a> SearchResults<'a> {
+    fn new(
+        mut results: Vec<SearchResult<'a>>,
+        filter: &SearchFilter,
+        cursor: Cursor,
+        max_results: Option<usize>,
+    ) -> Self {
+        let max_results = max_results.unwrap_or(5);
+
+        let matches = results.len();
+        if !filter.include_deprecated {
+            results.retain(|result| result.deprecated.is_none());
+        }
+        let omitted_deprecated = matches - results.len();
+
+        // break ties on the path, then the kind and id of items that share a path, so pages are
+        // the same between calls
+        results.sort_by(|a, b| {
+            a.matched
+                .cmp(&b.matched)
+                .then_with(|| b.score.total_cmp(&a.score))
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| (a.kind as u8).cmp(&(b.kind as u8)))
+                .then_with(|| (a.source, a.id).cmp(&(b.source, b.id)))
+        });
+
+        let total = results.len();
+        results.drain(..cursor.offset().min(total));
+        let omitted = results.len().saturating_sub(max_results);
+        results.truncate(max_results);
+        let next_cursor = Cursor::next(cursor.offset() + results.len(), total);
+
+        // groups are ordered by their best result
+        let mut parents: Vec<&str> = vec![];
+        for result in &results {
+            if !parents.contains(&result.parent()) {
+                parents.push(result.parent());
+            }
+        }
+        let parents: Vec<String> = parents.into_iter().map(String::from).collect();
+        results.sort_by_key(|result| parents.iter().position(|p| p == result.parent()));
+
+        Self {
+            results,
+            omitted,
+            omitted_deprecated,
+            next_cursor,
+        }
+    }
+}
+
+impl<'a> ops::Deref for SearchResults<'a> {
+    type Target = [SearchResult<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.results
+    }
+}
+
+impl Serialize for SearchResults<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Group<'a> {
+            parent: &'a str,
+            results: &'a [SearchResult<'a>],
+        }
+
+        #[derive(Serialize)]
+        struct Results<'a> {
+            groups: Vec<Group<'a>>,
+            omitted: usize,
+            omitted_deprecated: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            next_cursor: Option<Cursor>,
+        }
+
+        let groups = self
+            .results
+            .chunk_by(|a, b| a.parent() == b.parent())
+            .map(|results| Group {
+                parent: results[0].parent(),
+                results,
+            })
+            .collect();
+
+        Results {
+            groups,
+            omitted: self.omitted,
+            omitted_deprecated: self.omitted_deprecated,
+            next_cursor: self.next_cursor,
+        }
+        .serialize(serializer)
+    }
 }
 
 impl ops::Deref for SearchResult<'_> {
@@ -386,6 +1544,8 @@ impl ops::Deref for SearchResult<'_> {
 
 pub struct RustdocProvider {
     cache: Arc<Mutex<HashMap<String, Arc<Crate>>>>,
+    /// Where the sources of documented crates.io crates are kept so spans can be read later
+    sources_dir: PathBuf,
 }
 
 impl RustdocProvider {
@@ -394,18 +1554,30 @@ impl RustdocProvider {
         rustup_toolchain::install(NIGHTLY_VERSION)
             .map_err(|e| format!("Failed to install nightly toolchain: {e}"))?;
 
+        let sources_dir = std::env::temp_dir().join("cargo-mcp").join("sources");
+        std::fs::create_dir_all(&sources_dir)
+            .map_err(|e| format!("Failed to create sources dir: {e}"))?;
+
         Ok(Self {
             cache: Default::default(),
+            sources_dir,
         })
     }
 
-    pub async fn get_crate_docs(&self, name: &str, version: Option<&str>) -> Result<Arc<Crate>> {
+    pub async fn get_crate_docs(
+        &self,
+        name: &str,
+        version: Option<&str>,
+        features: &Features,
+    ) -> Result<Arc<Crate>> {
         let version = version.unwrap_or("*");
-        let cache_key = format!("{name}:{version}");
+        let cache_key = format!("{name}:{version}:{}", features.cache_key());
 
-        // Check cache first
-        if let Some(krate) = self.cache.lock().unwrap().get(&cache_key).cloned() {
-            return Ok(krate.clone());
+        // Check cache first, unless the copied sources have since been cleaned up
+        if let Some(krate) = self.cache.lock().unwrap().get(&cache_key).cloned()
+            && krate.has_sources()
+        {
+            return Ok(krate);
         }
 
         // Create temporary workspace
@@ -413,6 +1585,14 @@ impl RustdocProvider {
             tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {e}"))?;
 
         // Create Cargo.toml with the crate as a dependency
+        let mut dependency = format!("version = {version:?}");
+        if !features.features.is_empty() {
+            dependency.push_str(&format!(", features = {:?}", features.features));
+        }
+        if features.no_default_features {
+            dependency.push_str(", default-features = false");
+        }
+
         let cargo_toml = format!(
             r#"[package]
 name = "temp-workspace"
@@ -420,7 +1600,7 @@ version = "0.1.0"
 edition = "2024"
 
 [dependencies]
-{name} = {version:?}
+{name} = {{ {dependency} }}
 "#
         );
 
@@ -445,22 +1625,9 @@ edition = "2024"
             return Err("cargo vendor failed".into());
         }
 
-        // Find the vendored crate directory
+        // Generate and process the rustdoc JSON
         let vendor_dir = temp_dir.path().join("vendor");
-        let entries = std::fs::read_dir(&vendor_dir)
-            .map_err(|e| format!("Failed to read vendor dir: {e}"))?;
-
-        let crate_dir = entries
-            .filter_map(|e| e.ok())
-            .find(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false) && e.file_name() == name)
-            .ok_or_else(|| format!("Could not find vendored crate {name}"))?
-            .path();
-
-        // Generate rustdoc JSON
-        let raw_krate = self.generate_rustdoc_json(&crate_dir, false)?;
-
-        // Process and cache
-        let krate = Crate::from_crate(&raw_krate, None);
+        let krate = self.document_vendored(&vendor_dir, name, features, MAX_REEXPORT_DEPTH)?;
         let krate = Arc::new(krate);
 
         // Cache
@@ -472,34 +1639,168 @@ edition = "2024"
         Ok(krate)
     }
 
-    pub async fn get_workspace_docs(&self, path: &Path) -> Result<Arc<Crate>> {
+    /// Generates documentation for a workspace member, including private items
+    ///
+    /// The library target is documented unless `bin` names one of the package's binaries.
+    /// Packages without a library fall back to their only binary target.
+    pub async fn get_workspace_docs(
+        &self,
+        package: &Package,
+        bin: Option<&str>,
+        features: &Features,
+    ) -> Result<Arc<Crate>> {
+        self.document_workspace(package, bin, features, true)
+    }
+
+    /// Generates documentation for the public API of a workspace member's library
+    pub async fn get_workspace_api(
+        &self,
+        package: &Package,
+        features: &Features,
+    ) -> Result<Arc<Crate>> {
+        self.document_workspace(package, None, features, false)
+    }
+
+    fn document_workspace(
+        &self,
+        package: &Package,
+        bin: Option<&str>,
+        features: &Features,
+        private_items: bool,
+    ) -> Result<Arc<Crate>> {
+        let target = package_target(package, bin)?;
+
+        let builder = rustdoc_builder(package.manifest_path.as_std_path(), features)
+            .package(&*package.name)
+            .package_target(target)
+            .document_private_items(private_items);
+
         // For workspace docs, always regenerate since they can change frequently
-        let raw_krate = self.generate_rustdoc_json(path, true)?;
+        let raw_krate = self.generate_rustdoc_json(builder)?;
 
-        // Process and cache both versions
-        let krate = Crate::from_crate(&raw_krate, None);
+        let dir = package
+            .manifest_path
+            .parent()
+            .unwrap_or(&package.manifest_path);
+        let krate = Crate::from_crate(raw_krate, dir.as_std_path(), &mut |_| None);
         let krate = Arc::new(krate);
 
         Ok(krate)
     }
 
-    fn generate_rustdoc_json(
+    /// Documents a vendored crate, along with any vendored crates it re-exports items from
+    ///
+    /// The crates it re-exports from are documented with the features that `features` enables
+    /// on them.
+    fn document_vendored(
         &self,
-        path: &Path,
-        is_workspace: bool,
-    ) -> Result<rustdoc_types::Crate> {
-        // If path is a directory, append Cargo.toml
-        let manifest_path = if path.ends_with("Cargo.toml") {
-            path.to_path_buf()
-        } else {
-            path.join("Cargo.toml")
+        vendor_dir: &Path,
+        name: &str,
+        features: &Features,
+        depth: usize,
+    ) -> Result<Crate> {
+        let crate_dir = find_vendored(vendor_dir, name)
+            .ok_or_else(|| format!("Could not find vendored crate {name}"))?;
+
+        let builder = rustdoc_builder(&crate_dir.join("Cargo.toml"), features);
+        let raw_krate = self.generate_rustdoc_json(builder)?;
+
+        let mut warnings = vec![];
+        let package = match MetadataCommand::new()
+            .manifest_path(crate_dir.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+        {
+            Ok(metadata) => metadata.root_package().cloned(),
+            Err(err) => {
+                warnings.push(format!(
+                    "Failed to read the manifest of {name}, so the crates it re-exports from are \
+                     documented with their default features: {err}"
+                ));
+                None
+            }
         };
 
+        // The vendor directory is removed once documentation is generated
+        let crate_dir = self.persist_source(&crate_dir, &raw_krate)?;
+
+        let mut krate = Crate::from_crate(raw_krate, &crate_dir, &mut |dependency| {
+            // Re-exports from the standard library won't be vendored
+            find_vendored(vendor_dir, dependency)?;
+
+            if depth == 0 {
+                return None;
+            }
+
+            let features = match &package {
+                Some(package) => dependency_features(package, features, dependency),
+                None => Features::default(),
+            };
+            match self.document_vendored(vendor_dir, dependency, &features, depth - 1) {
+                Ok(krate) => Some(krate),
+                Err(err) => {
+                    warnings.push(format!(
+                        "Failed to document re-exported crate {dependency}: {err}"
+                    ));
+                    None
+                }
+            }
+        });
+        krate.warnings.splice(0..0, warnings);
+
+        Ok(krate)
+    }
+
+    /// Copies a vendored crate into the sources directory, returning the location of the copy
+    fn persist_source(&self, crate_dir: &Path, krate: &rustdoc_types::Crate) -> Result<PathBuf> {
+        let dir_name = crate_dir
+            .file_name()
+            .ok_or_else(|| format!("Invalid crate directory {}", crate_dir.display()))?
+            .to_string_lossy();
+        // vendored directories only include the version when there are several of them
+        let dir_name = match &krate.crate_version {
+            Some(version) if !dir_name.ends_with(version.as_str()) => {
+                format!("{dir_name}-{version}")
+            }
+            _ => dir_name.to_string(),
+        };
+
+        // published crates never change so an existing copy can be reused, unless some of its
+        // files were cleaned up from the temporary directory
+        let dest = self.sources_dir.join(dir_name);
+        if dest.exists() {
+            if has_sources(krate, &dest) {
+                return Ok(dest);
+            }
+            if let Err(err) = std::fs::remove_dir_all(&dest)
+                && err.kind() != std::io::ErrorKind::NotFound
+            {
+                return Err(format!("Failed to remove incomplete sources: {err}").into());
+            }
+        }
+
+        // copy to a staging directory first so a partial copy is never used
+        let staging = tempfile::tempdir_in(&self.sources_dir)
+            .map_err(|e| format!("Failed to create staging dir: {e}"))?;
+        let staged = staging.path().join("src");
+        copy_dir(crate_dir, &staged)
+            .map_err(|e| format!("Failed to copy sources of {}: {e}", crate_dir.display()))?;
+
+        if let Err(err) = std::fs::rename(&staged, &dest)
+            && !dest.exists()
+        {
+            return Err(format!("Failed to persist sources to {}: {err}", dest.display()).into());
+        }
+
+        Ok(dest)
+    }
+
+    fn generate_rustdoc_json(
+        &self,
+        builder: rustdoc_json::Builder,
+    ) -> Result<rustdoc_types::Crate> {
         // Generate rustdoc JSON
-        let json_path = rustdoc_json::Builder::default()
-            .toolchain(NIGHTLY_VERSION)
-            .manifest_path(&manifest_path)
-            .document_private_items(is_workspace) // Include private items
+        let json_path = builder
             .build()
             .map_err(|e| format!("Failed to generate rustdoc JSON: {e}"))?;
 
@@ -518,14 +1819,14 @@ edition = "2024"
                     return Err(err);
                 };
 
-                if let Some(format_version) = value.get("format_version").and_then(|v| v.as_u64()) {
-                    if rustdoc_types::FORMAT_VERSION as u64 != format_version {
-                        return Err(format!(
-                            "rustdoc JSON format version mismatch: expected {}, got {format_version}",
-                            rustdoc_types::FORMAT_VERSION,
-                        )
-                        .into());
-                    }
+                if let Some(format_version) = value.get("format_version").and_then(|v| v.as_u64())
+                    && rustdoc_types::FORMAT_VERSION as u64 != format_version
+                {
+                    return Err(format!(
+                        "rustdoc JSON format version mismatch: expected {}, got {format_version}",
+                        rustdoc_types::FORMAT_VERSION,
+                    )
+                    .into());
                 }
 
                 Err(err)
@@ -534,6 +1835,167 @@ edition = "2024"
     }
 }
 
+/// Returns the features that documenting `package` with `features` enables on `dependency`
+///
+/// Enabling all features of the package enables all features of the dependency, since those
+/// are the items that could become reachable.
+pub fn dependency_features(package: &Package, features: &Features, dependency: &str) -> Features {
+    if features.all_features {
+        return Features {
+            all_features: true,
+            ..Features::default()
+        };
+    }
+
+    let normalize = |name: &str| name.replace('-', "_");
+    let Some(declared) = package.dependencies.iter().find(|dep| {
+        dep.kind == DependencyKind::Normal
+            && normalize(dep.rename.as_deref().unwrap_or(&dep.name)) == normalize(dependency)
+    }) else {
+        return Features::default();
+    };
+    // features refer to dependencies by the name they're imported with
+    let key = declared.rename.as_deref().unwrap_or(&declared.name);
+
+    let mut enabled = declared.features.clone();
+    let mut stack: Vec<_> = features.features.iter().map(String::as_str).collect();
+    if !features.no_default_features {
+        stack.push("default");
+    }
+    let mut seen = HashSet::new();
+    while let Some(feature) = stack.pop() {
+        if !seen.insert(feature) {
+            continue;
+        }
+        for value in package.features.get(feature).into_iter().flatten() {
+            match value.split_once('/') {
+                Some((dep, dep_feature)) if dep.trim_end_matches('?') == key => {
+                    enabled.push(dep_feature.to_string());
+                }
+                Some(_) => {}
+                None if value.starts_with("dep:") => {}
+                None => stack.push(value),
+            }
+        }
+    }
+    enabled.sort();
+    enabled.dedup();
+
+    Features {
+        features: enabled,
+        all_features: false,
+        no_default_features: !declared.uses_default_features,
+    }
+}
+
+fn rustdoc_builder(manifest_path: &Path, features: &Features) -> rustdoc_json::Builder {
+    rustdoc_json::Builder::default()
+        .toolchain(NIGHTLY_VERSION)
+        .manifest_path(manifest_path)
+        .features(&features.features)
+        .all_features(features.all_features)
+        .no_default_features(features.no_default_features)
+}
+
+/// Selects the target of a package to document
+fn package_target(package: &Package, bin: Option<&str>) -> Result<PackageTarget> {
+    let bins = || {
+        package
+            .targets
+            .iter()
+            .filter(|target| target.is_bin())
+            .map(|target| target.name.as_str())
+    };
+
+    if let Some(bin) = bin {
+        if !bins().any(|name| name == bin) {
+            return Err(format!(
+                "Could not find binary target {bin:?} in crate {}. Available binaries: {:?}",
+                package.name,
+                bins().collect::<Vec<_>>(),
+            )
+            .into());
+        }
+        return Ok(PackageTarget::Bin(bin.to_string()));
+    }
+
+    let is_lib = |kind: &TargetKind| {
+        matches!(
+            kind,
+            TargetKind::Lib
+                | TargetKind::RLib
+                | TargetKind::DyLib
+                | TargetKind::CDyLib
+                | TargetKind::StaticLib
+                | TargetKind::ProcMacro
+        )
+    };
+    if package
+        .targets
+        .iter()
+        .any(|target| target.kind.iter().any(is_lib))
+    {
+        return Ok(PackageTarget::Lib);
+    }
+
+    match bins().collect::<Vec<_>>()[..] {
+        [bin] => Ok(PackageTarget::Bin(bin.to_string())),
+        [] => Err(format!("Crate {} has no library or binary targets", package.name).into()),
+        ref bins => Err(format!(
+            "Crate {} has no library target. Specify one of the binaries: {bins:?}",
+            package.name,
+        )
+        .into()),
+    }
+}
+
+/// Finds the directory of a crate in the output of `cargo vendor`
+///
+/// Crate names are matched regardless of `-` and `_`, and also match directories that are
+/// suffixed with a version, which `cargo vendor` uses when multiple versions are vendored.
+fn find_vendored(vendor_dir: &Path, name: &str) -> Option<std::path::PathBuf> {
+    let name = name.replace('-', "_");
+    let mut versioned = None;
+
+    for entry in std::fs::read_dir(vendor_dir).ok()?.filter_map(|e| e.ok()) {
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy().replace('-', "_");
+        if file_name == name {
+            return Some(entry.path());
+        }
+
+        let is_versioned = file_name
+            .strip_prefix(&name)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+        if is_versioned && versioned.is_none() {
+            versioned = Some(entry.path());
+        }
+    }
+
+    versioned
+}
+
+/// Recursively copies a directory, skipping any build output
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != "target" {
+                copy_dir(&path, &to.join(entry.file_name()))?;
+            }
+        } else {
+            std::fs::copy(&path, to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
 fn deserialize_str<T: serde::de::DeserializeOwned>(v: &str) -> Result<T> {
     let mut deserializer = serde_json::Deserializer::from_str(v);
     deserializer.disable_recursion_limit();
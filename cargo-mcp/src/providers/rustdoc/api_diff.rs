@@ -0,0 +1,539 @@
+//! Compares the public API of two versions of a crate
+//!
+//! Changes are classified following the Cargo semver guidelines:
+//! <https://doc.rust-lang.org/cargo/reference/semver.html>
+
+use super::{
+    Crate, Item, Str, TraitItem,
+    signature::{self, Render},
+};
+use crate::types::{ApiChange, ChangeKind};
+use rustdoc_types::{ItemEnum, ItemKind, StructKind};
+use semver::Version;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Returns the changes to the public API from `old` to `new`
+///
+/// Breaking changes come first, followed by the rest in path order.
+pub fn diff(old: &Crate, new: &Crate) -> Vec<ApiChange> {
+    let old_api = Api::new(old);
+    let new_api = Api::new(new);
+    let mut changes = vec![];
+
+    // paths that no longer resolve, along with their parents so children aren't reported twice
+    let mut removed = BTreeSet::new();
+    let mut added = BTreeSet::new();
+
+    for (path, entry) in &old_api.entries {
+        let Some(new_entry) = new_api.entries.get(path) else {
+            if parent(path).is_some_and(|parent| removed.contains(parent)) {
+                removed.insert(path.as_str());
+                continue;
+            }
+            removed.insert(path.as_str());
+
+            // the item may still be available at another path, either because only a re-export
+            // was removed or because it was moved to a different module. Unrelated items can
+            // share a name, so a move also needs the same signature and no other candidate.
+            let moved_to =
+                if *entry.canonical != **path && new_api.entries.contains_key(&*entry.canonical) {
+                    Some(&*entry.canonical)
+                } else if old_api.in_module(path) && entry.signature.is_some() {
+                    let mut candidates = new_api.entries.iter().filter(|(new_path, new_entry)| {
+                        new_entry.name == entry.name
+                            && new_entry.kind == entry.kind
+                            && same_signature(entry, new_entry)
+                            && new_api.in_module(new_path)
+                            && !old_api.entries.contains_key(*new_path)
+                    });
+                    match (candidates.next(), candidates.next()) {
+                        (Some((new_path, _)), None) => Some(new_path.as_str()),
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+
+            changes.push(match moved_to {
+                Some(to) => {
+                    if !old_api.entries.contains_key(to) {
+                        added.insert(to);
+                    }
+                    ApiChange {
+                        path: path.clone(),
+                        kind: entry.kind,
+                        change: ChangeKind::Moved,
+                        breaking: true,
+                        description: format!("moved to `{to}`"),
+                        old: Some(path.clone()),
+                        new: Some(to.to_string()),
+                    }
+                }
+                None => ApiChange {
+                    path: path.clone(),
+                    kind: entry.kind,
+                    change: ChangeKind::Removed,
+                    breaking: true,
+                    description: format!("{} removed", describe(entry.kind)),
+                    old: entry.signature.clone(),
+                    new: None,
+                },
+            });
+            continue;
+        };
+
+        // re-exports share an entry, so only compare an item once
+        if *entry.canonical != **path {
+            continue;
+        }
+
+        compare(path, entry, new_entry, &mut changes);
+    }
+
+    for (path, entry) in &new_api.entries {
+        if old_api.entries.contains_key(path) || added.contains(path.as_str()) {
+            continue;
+        }
+        let parent_path = parent(path);
+        if parent_path.is_some_and(|parent| added.contains(parent)) {
+            added.insert(path.as_str());
+            continue;
+        }
+        added.insert(path.as_str());
+
+        let old_parent = parent_path.and_then(|parent| old_api.entries.get(parent));
+        let (breaking, description) = match entry.kind {
+            ItemKind::Variant if old_parent.is_some_and(|parent| !parent.non_exhaustive) => {
+                (true, "variant added to an exhaustive enum".to_string())
+            }
+            ItemKind::StructField
+                if old_parent
+                    .is_some_and(|parent| !parent.non_exhaustive && !parent.private_fields) =>
+            {
+                (
+                    true,
+                    "public field added to a struct with only public fields".to_string(),
+                )
+            }
+            _ if entry.trait_item == Some(TraitItem::Required) && old_parent.is_some() => {
+                (true, "item without a default added to a trait".to_string())
+            }
+            kind => (false, format!("{} added", describe(kind))),
+        };
+
+        changes.push(ApiChange {
+            path: path.clone(),
+            kind: entry.kind,
+            change: ChangeKind::Added,
+            breaking,
+            description,
+            old: None,
+            new: entry.signature.clone(),
+        });
+    }
+
+    changes.sort_by(|a, b| {
+        b.breaking
+            .cmp(&a.breaking)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    changes
+}
+
+/// Compares an item that exists in both versions
+fn compare(path: &str, old: &Entry, new: &Entry, changes: &mut Vec<ApiChange>) {
+    let mut change = |breaking: bool, description: String, before, after| {
+        changes.push(ApiChange {
+            path: path.to_string(),
+            kind: new.kind,
+            change: ChangeKind::Changed,
+            breaking,
+            description,
+            old: before,
+            new: after,
+        })
+    };
+
+    if old.kind != new.kind {
+        change(
+            true,
+            format!(
+                "changed from {} to {}",
+                describe(old.kind),
+                describe(new.kind)
+            ),
+            old.signature.clone(),
+            new.signature.clone(),
+        );
+        return;
+    }
+
+    if !same_signature(old, new) {
+        change(
+            true,
+            "signature changed".to_string(),
+            old.signature.clone(),
+            new.signature.clone(),
+        );
+    }
+
+    if !old.non_exhaustive && new.non_exhaustive {
+        change(true, "marked `#[non_exhaustive]`".to_string(), None, None);
+    } else if old.non_exhaustive && !new.non_exhaustive {
+        change(
+            false,
+            "no longer `#[non_exhaustive]`".to_string(),
+            None,
+            None,
+        );
+    }
+
+    if !old.private_fields && new.private_fields && !old.non_exhaustive {
+        change(
+            true,
+            "private field added to a struct with only public fields".to_string(),
+            None,
+            None,
+        );
+    }
+
+    if old.trait_item == Some(TraitItem::Provided) && new.trait_item == Some(TraitItem::Required) {
+        change(
+            true,
+            "default implementation removed".to_string(),
+            None,
+            None,
+        );
+    }
+
+    for trait_impl in old.trait_impls.difference(&new.trait_impls) {
+        change(
+            true,
+            format!("no longer implements `{trait_impl}`"),
+            Some(trait_impl.to_string()),
+            None,
+        );
+    }
+    for trait_impl in new.trait_impls.difference(&old.trait_impls) {
+        change(
+            false,
+            format!("now implements `{trait_impl}`"),
+            None,
+            Some(trait_impl.to_string()),
+        );
+    }
+}
+
+/// The parts of an item that affect compatibility
+struct Entry {
+    name: Str,
+    /// The canonical path of the item, which is shared by its re-exports
+    canonical: Str,
+    kind: ItemKind,
+    signature: Option<String>,
+    trait_impls: BTreeSet<Str>,
+    trait_item: Option<TraitItem>,
+    non_exhaustive: bool,
+    /// Whether a struct has any fields that aren't public
+    private_fields: bool,
+}
+
+/// The public items of a crate keyed by every path they're reachable at
+struct Api {
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Api {
+    fn new(krate: &Crate) -> Self {
+        let mut entries = BTreeMap::new();
+
+        for item in krate.items.values() {
+            // fields are added along with their struct, under each of its paths
+            if !item.is_public
+                || matches!(
+                    item.kind,
+                    ItemKind::Impl | ItemKind::Use | ItemKind::StructField
+                )
+            {
+                continue;
+            }
+
+            let info = krate.raw(item).index.get(&item.id);
+            let non_exhaustive = item
+                .attributes
+                .iter()
+                .any(|attr| &**attr == "non_exhaustive");
+
+            let mut private_fields = false;
+            let mut fields = vec![];
+            if let Some(ItemEnum::Struct(s)) = info.map(|info| &info.inner) {
+                let raw = krate.raw(item);
+                let ids: Vec<_> = match &s.kind {
+                    StructKind::Unit => vec![],
+                    StructKind::Tuple(fields) => {
+                        private_fields |= fields.iter().any(Option::is_none);
+                        fields.iter().flatten().collect()
+                    }
+                    StructKind::Plain {
+                        fields,
+                        has_stripped_fields,
+                    } => {
+                        private_fields |= *has_stripped_fields;
+                        fields.iter().collect()
+                    }
+                };
+
+                for (idx, id) in ids.into_iter().enumerate() {
+                    let Some(field) = raw.index.get(id) else {
+                        continue;
+                    };
+                    if !matches!(field.visibility, rustdoc_types::Visibility::Public) {
+                        private_fields = true;
+                        continue;
+                    }
+                    let ItemEnum::StructField(ty) = &field.inner else {
+                        continue;
+                    };
+                    let name = field.name.clone().unwrap_or_else(|| idx.to_string());
+                    fields.push((name, ty.to_source()));
+                }
+            }
+
+            for path in item.paths() {
+                for (name, ty) in &fields {
+                    let field_path = format!("{path}::{name}");
+                    entries.insert(
+                        field_path.clone(),
+                        Entry {
+                            name: name.as_str().into(),
+                            canonical: format!("{}::{name}", item.path).into(),
+                            kind: ItemKind::StructField,
+                            signature: Some(format!("pub {name}: {ty}")),
+                            trait_impls: BTreeSet::new(),
+                            trait_item: None,
+                            non_exhaustive: false,
+                            private_fields: false,
+                        },
+                    );
+                }
+
+                entries.insert(
+                    path.to_string(),
+                    Entry {
+                        name: item.name.clone(),
+                        canonical: item.path.clone(),
+                        kind: item.kind,
+                        signature: item.signature.as_deref().map(String::from),
+                        trait_impls: trait_impls(krate, item),
+                        trait_item: item.trait_item,
+                        non_exhaustive,
+                        private_fields,
+                    },
+                );
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Returns whether the item at `path` is directly inside of a module rather than a type
+    fn in_module(&self, path: &str) -> bool {
+        parent(path)
+            .and_then(|parent| self.entries.get(parent))
+            .is_some_and(|parent| parent.kind == ItemKind::Module)
+    }
+}
+
+/// Returns the traits a type implements, leaving out blanket impls since those follow from the
+/// other impls
+fn trait_impls(krate: &Crate, item: &Item) -> BTreeSet<Str> {
+    let raw = krate.raw(item);
+    let impls = match raw.index.get(&item.id).map(|info| &info.inner) {
+        Some(ItemEnum::Struct(s)) => &s.impls[..],
+        Some(ItemEnum::Union(u)) => &u.impls[..],
+        Some(ItemEnum::Enum(e)) => &e.impls[..],
+        _ => return BTreeSet::new(),
+    };
+
+    impls
+        .iter()
+        .filter_map(|id| match &raw.index.get(id)?.inner {
+            ItemEnum::Impl(impl_) if impl_.blanket_impl.is_none() => {
+                signature::trait_impl(impl_, raw).map(Str::from)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns whether two entries have signatures that are the same to callers
+fn same_signature(old: &Entry, new: &Entry) -> bool {
+    old.signature.as_deref().map(canonical) == new.signature.as_deref().map(canonical)
+}
+
+/// Rewrites a signature so that changes that don't affect callers compare equal
+///
+/// Lifetimes are numbered in the order they appear, paths are reduced to their last segment,
+/// e.g. `std::fmt::Result` to `Result`, and the predicates of a `where` clause are sorted.
+fn canonical(signature: &str) -> String {
+    let chars: Vec<char> = signature.chars().collect();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut lifetimes: Vec<String> = vec![];
+    let mut out = String::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c == '\'' {
+            let start = idx + 1;
+            idx = start;
+            while idx < chars.len() && is_ident(chars[idx]) {
+                idx += 1;
+            }
+            let name: String = chars[start..idx].iter().collect();
+            if name == "static" || name == "_" {
+                out.push('\'');
+                out.push_str(&name);
+            } else {
+                let position = lifetimes
+                    .iter()
+                    .position(|l| *l == name)
+                    .unwrap_or_else(|| {
+                        lifetimes.push(name);
+                        lifetimes.len() - 1
+                    });
+                out.push_str(&format!("'{position}"));
+            }
+        } else if is_ident(c) {
+            // keep only the last segment of a path
+            let mut start = idx;
+            while idx < chars.len() && is_ident(chars[idx]) {
+                idx += 1;
+                if chars[idx..].starts_with(&[':', ':'])
+                    && chars.get(idx + 2).is_some_and(|&c| is_ident(c))
+                {
+                    idx += 2;
+                    start = idx;
+                }
+            }
+            out.extend(&chars[start..idx]);
+        } else if c.is_whitespace() {
+            while idx < chars.len() && chars[idx].is_whitespace() {
+                idx += 1;
+            }
+            out.push(' ');
+        } else {
+            out.push(c);
+            idx += 1;
+        }
+    }
+
+    let Some((head, predicates)) = out.split_once(" where ") else {
+        return out;
+    };
+    let mut predicates = split_top_level(predicates);
+    predicates.sort_unstable();
+    format!("{head} where {}", predicates.join(", "))
+}
+
+/// Splits a list on the commas that aren't nested in brackets
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (idx, c) in list.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            // the `>` of `->` doesn't close a bracket
+            '>' if prev != '-' => depth -= 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(list[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(list[start..].trim());
+    parts
+}
+
+fn parent(path: &str) -> Option<&str> {
+    path.rsplit_once("::").map(|(parent, _)| parent)
+}
+
+/// Returns a readable name for a kind of item
+fn describe(kind: ItemKind) -> String {
+    let kind = serde_json::to_value(kind)
+        .ok()
+        .and_then(|kind| kind.as_str().map(String::from))
+        .unwrap_or_else(|| "item".to_string());
+    kind.replace('_', " ")
+}
+
+/// How much a version number changes, following Cargo's compatibility rules
+///
+/// For `0.x` versions a `Major` bump increments the minor version and a `Minor` bump the patch
+/// version, since Cargo treats `0.x.y` and `0.x.z` as compatible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// Returns the smallest bump that allows for `changes`
+    pub fn required(changes: &[ApiChange]) -> Self {
+        if changes.iter().any(|change| change.breaking) {
+            Self::Major
+        } else if !changes.is_empty() {
+            Self::Minor
+        } else {
+            Self::Patch
+        }
+    }
+
+    /// Returns the bump from `old` to `new`
+    pub fn between(old: &Version, new: &Version) -> Self {
+        if new <= old {
+            return Self::None;
+        }
+
+        if compatibility(old) != compatibility(new) {
+            Self::Major
+        } else if old.major == 0 || old.minor != new.minor {
+            // additions to `0.x` versions only need a patch release
+            Self::Minor
+        } else {
+            Self::Patch
+        }
+    }
+
+    /// Returns the next version after `version` with this bump
+    pub fn apply(self, version: &Version) -> Version {
+        let (major, minor, patch) = (version.major, version.minor, version.patch);
+        let (major, minor, patch) = match self {
+            Self::None => (major, minor, patch),
+            Self::Major if major > 0 => (major + 1, 0, 0),
+            Self::Major if minor > 0 => (0, minor + 1, 0),
+            Self::Minor if major > 0 => (major, minor + 1, 0),
+            Self::Major | Self::Minor | Self::Patch => (major, minor, patch + 1),
+        };
+        Version::new(major, minor, patch)
+    }
+}
+
+/// Returns the part of a version that must match for Cargo to consider versions compatible
+fn compatibility(version: &Version) -> (u64, u64, u64) {
+    match (version.major, version.minor) {
+        (0, 0) => (0, 0, version.patch),
+        (0, minor) => (0, minor, 0),
+        (major, _) => (major, 0, 0),
+    }
+}
@@ -0,0 +1,130 @@
+//! Extracts the attributes that affect how an item can be used, e.g. `#[must_use]`
+
+use super::Str;
+use crate::types::Deprecation;
+use rustdoc_types::{Item, ItemEnum};
+
+/// Returns the notable attributes of an item, written like they are in source without the
+/// `#[..]`, e.g. `non_exhaustive` or `repr(C)`
+///
+/// Unsafe functions, traits and mutable statics are marked with `unsafe`. Crates that use
+/// `staged_api`, like the standard library, also mark items with `stable(..)` or `unstable(..)`.
+pub fn notable(info: &Item) -> Vec<Str> {
+    let mut attrs = vec![];
+
+    for attr in &info.attrs {
+        let Some(attr) = attr
+            .trim()
+            .strip_prefix("#[")
+            .and_then(|attr| attr.strip_suffix(']'))
+        else {
+            continue;
+        };
+
+        // some attributes are rendered from the compiler's parsed form, e.g.
+        // `#[attr = MustUse {reason: "..."}]`
+        if let Some(parsed) = attr.strip_prefix("attr = ") {
+            if let Some(rest) = parsed.strip_prefix("MustUse") {
+                attrs.push(must_use(reason(rest)));
+            } else if let Some(rest) = parsed.strip_prefix("Stability") {
+                attrs.extend(stability(rest));
+            }
+            continue;
+        }
+
+        if attr == "must_use" {
+            attrs.push(must_use(None));
+        } else if let Some(rest) = attr.strip_prefix("must_use") {
+            let reason = rest.trim_start().strip_prefix('=').map(str::trim);
+            attrs.push(must_use(reason.map(|reason| reason.trim_matches('"'))));
+        } else if attr == "non_exhaustive"
+            || ["repr(", "stable(", "unstable("]
+                .iter()
+                .any(|prefix| attr.starts_with(prefix))
+        {
+            attrs.push(attr.split_whitespace().collect::<Vec<_>>().join(" ").into());
+        }
+    }
+
+    let is_unsafe = match &info.inner {
+        ItemEnum::Function(function) => function.header.is_unsafe,
+        ItemEnum::Trait(t) => t.is_unsafe,
+        ItemEnum::Static(s) => s.is_unsafe || s.is_mutable,
+        _ => false,
+    };
+    if is_unsafe {
+        attrs.push("unsafe".into());
+    }
+
+    attrs
+}
+
+/// Returns the deprecation notice of an item, if it's deprecated
+pub fn deprecation(info: &Item) -> Option<Deprecation> {
+    let deprecation = info.deprecation.as_ref()?;
+    Some(Deprecation {
+        since: deprecation.since.clone(),
+        note: deprecation.note.clone(),
+    })
+}
+
+fn must_use(reason: Option<&str>) -> Str {
+    match reason {
+        Some(reason) => format!("must_use = \"{reason}\"").into(),
+        None => "must_use".into(),
+    }
+}
+
+/// Renders the parsed form of a stability attribute like it's written in source, e.g.
+/// `{stability: Stability {level: Unstable {issue: 123, ..}, feature: "name"}}` as
+/// `unstable(feature = "name", issue = "123")`
+fn stability(fields: &str) -> Option<Str> {
+    // the feature comes last, after a reason that could contain anything
+    let (_, feature) = fields.rsplit_once("feature:")?;
+    let feature = feature.trim_start().strip_prefix('"')?.split('"').next()?;
+
+    let (_, level) = fields.split_once("level:")?;
+    let level = level.trim_start();
+    if level.starts_with("Stable") {
+        let version = ["major", "minor", "patch"]
+            .map(|part| field(level, part))
+            .into_iter()
+            .collect::<Option<Vec<_>>>();
+        Some(
+            match version {
+                Some(version) => format!(
+                    "stable(feature = \"{feature}\", since = \"{}\")",
+                    version.join(".")
+                ),
+                None => format!("stable(feature = \"{feature}\")"),
+            }
+            .into(),
+        )
+    } else if level.starts_with("Unstable") {
+        Some(
+            match field(level, "issue") {
+                Some(issue) => format!("unstable(feature = \"{feature}\", issue = \"{issue}\")"),
+                None => format!("unstable(feature = \"{feature}\")"),
+            }
+            .into(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Returns the value of the first `name: value` field in `fields`
+fn field<'a>(fields: &'a str, name: &str) -> Option<&'a str> {
+    let (_, rest) = fields.split_once(&format!("{name}:"))?;
+    let rest = rest.trim_start();
+    let end = rest.find([',', '}', ')']).unwrap_or(rest.len());
+    Some(rest[..end].trim())
+}
+
+/// Parses the reason out of `{reason: "..."}`
+fn reason(fields: &str) -> Option<&str> {
+    let (_, rest) = fields.split_once("reason:")?;
+    let rest = rest.trim_start().strip_prefix('"')?;
+    let (reason, _) = rest.rsplit_once('"')?;
+    Some(reason)
+}
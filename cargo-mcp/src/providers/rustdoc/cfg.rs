@@ -0,0 +1,290 @@
+//! Parses the `cfg` predicates that gate an item from its rustdoc attributes
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cfg {
+    Feature(String),
+    All(Vec<Cfg>),
+    Any(Vec<Cfg>),
+    Not(Box<Cfg>),
+    /// Any other predicate, e.g. `unix` or `target_os = "linux"`
+    Other(String),
+}
+
+impl Cfg {
+    /// Collects the predicates from `#[cfg(..)]` and `#[doc(cfg(..))]` attributes
+    pub fn from_attrs(attrs: &[String]) -> Option<Self> {
+        let mut predicates = vec![];
+
+        for attr in attrs {
+            let Some(attr) = attr
+                .trim()
+                .strip_prefix("#[")
+                .and_then(|attr| attr.strip_suffix(']'))
+            else {
+                continue;
+            };
+
+            let Some(meta) = Parser::new(attr).meta() else {
+                continue;
+            };
+
+            meta.collect(&mut predicates);
+        }
+
+        Self::all(predicates)
+    }
+
+    /// Combines two optional predicates that must both hold
+    pub fn and(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (Some(a), Some(b)) => Self::all(vec![a, b]),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn all(predicates: Vec<Self>) -> Option<Self> {
+        let mut flattened: Vec<Self> = vec![];
+        for predicate in predicates {
+            let predicates = match predicate {
+                Self::All(predicates) => predicates,
+                predicate => vec![predicate],
+            };
+            for predicate in predicates {
+                if !flattened.contains(&predicate) {
+                    flattened.push(predicate);
+                }
+            }
+        }
+
+        match flattened.len() {
+            0 => None,
+            1 => flattened.pop(),
+            _ => Some(Self::All(flattened)),
+        }
+    }
+
+    /// Returns the features that must all be enabled for the predicate to hold
+    ///
+    /// Features that are only required on one side of an `any(..)` or `not(..)` aren't included.
+    pub fn required_features(&self) -> Vec<String> {
+        let mut features = vec![];
+        self.push_required_features(&mut features);
+        features
+    }
+
+    fn push_required_features(&self, features: &mut Vec<String>) {
+        match self {
+            Self::Feature(feature) => {
+                if !features.contains(feature) {
+                    features.push(feature.clone());
+                }
+            }
+            Self::All(predicates) => {
+                for predicate in predicates {
+                    predicate.push_required_features(features);
+                }
+            }
+            Self::Any(_) | Self::Not(_) | Self::Other(_) => {}
+        }
+    }
+}
+
+impl fmt::Display for Cfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, name: &str, predicates: &[Cfg]| {
+            write!(f, "{name}(")?;
+            for (idx, predicate) in predicates.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{predicate}")?;
+            }
+            f.write_str(")")
+        };
+
+        match self {
+            Self::Feature(feature) => write!(f, "feature = {feature:?}"),
+            Self::All(predicates) => list(f, "all", predicates),
+            Self::Any(predicates) => list(f, "any", predicates),
+            Self::Not(predicate) => write!(f, "not({predicate})"),
+            Self::Other(predicate) => f.write_str(predicate),
+        }
+    }
+}
+
+impl Serialize for Cfg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A parsed attribute, e.g. `doc(cfg(feature = "x"))`
+#[derive(Debug)]
+enum Meta {
+    Word(String),
+    NameValue(String, String),
+    List(String, Vec<Meta>),
+}
+
+impl Meta {
+    fn name(&self) -> &str {
+        match self {
+            Self::Word(name) | Self::NameValue(name, _) | Self::List(name, _) => name,
+        }
+    }
+
+    /// Pushes the predicates of any `cfg` attributes
+    fn collect(&self, predicates: &mut Vec<Cfg>) {
+        let Self::List(name, items) = self else {
+            return;
+        };
+
+        match name.as_str() {
+            // newer nightlies print parsed attributes as `#[<cfg>(..)]`
+            "cfg" | "<cfg>" => {
+                if let [predicate] = &items[..] {
+                    predicates.push(predicate.cfg());
+                }
+            }
+            "doc" => {
+                for item in items {
+                    if item.name() == "cfg" {
+                        item.collect(predicates);
+                    }
+                }
+            }
+            // the condition is usually `docsrs`, so only the attributes matter
+            "cfg_attr" | "<cfg_attr>" => {
+                for item in items.iter().skip(1) {
+                    item.collect(predicates);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn cfg(&self) -> Cfg {
+        match self {
+            Self::NameValue(name, value) if name == "feature" => Cfg::Feature(value.clone()),
+            Self::List(name, items) if name == "all" => {
+                Cfg::All(items.iter().map(Self::cfg).collect())
+            }
+            Self::List(name, items) if name == "any" => {
+                Cfg::Any(items.iter().map(Self::cfg).collect())
+            }
+            Self::List(name, items) if name == "not" && items.len() == 1 => {
+                Cfg::Not(Box::new(items[0].cfg()))
+            }
+            other => Cfg::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Meta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(name) => f.write_str(name),
+            Self::NameValue(name, value) => write!(f, "{name} = {value:?}"),
+            Self::List(name, items) => {
+                write!(f, "{name}(")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    fn meta(&mut self) -> Option<Meta> {
+        let name = self.ident()?;
+
+        if self.eat('=') {
+            return Some(Meta::NameValue(name, self.string()?));
+        }
+
+        if !self.eat('(') {
+            return Some(Meta::Word(name));
+        }
+
+        let mut items = vec![];
+        loop {
+            if self.eat(')') {
+                break;
+            }
+            items.push(self.meta()?);
+            if !self.eat(',') {
+                if !self.eat(')') {
+                    return None;
+                }
+                break;
+            }
+        }
+
+        Some(Meta::List(name, items))
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let len = self
+            .input
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '<' | '>' | ':')))
+            .unwrap_or(self.input.len());
+        if len == 0 {
+            return None;
+        }
+        let (ident, rest) = self.input.split_at(len);
+        self.input = rest;
+        Some(ident.to_string())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let rest = self.input.strip_prefix('"')?;
+
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.input = &rest[idx + 1..];
+                    return Some(value);
+                }
+                '\\' => value.push(chars.next()?.1),
+                c => value.push(c),
+            }
+        }
+
+        None
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.input.strip_prefix(c) {
+            Some(rest) => {
+                self.input = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.input = self.input.trim_start();
+    }
+}
@@ -0,0 +1,169 @@
+//! Finds the ways to obtain a value of a type, e.g. `new` functions, `Default` and builders
+
+use super::{Crate, ImplKind, Item, Str, impls};
+use rustdoc_types::{GenericArg, GenericArgs, ItemEnum, ItemKind, Type};
+use serde::Serialize;
+
+/// A way to obtain a value of a type
+#[derive(Clone, Debug, Serialize)]
+pub struct Constructor {
+    pub kind: ConstructorKind,
+    /// The function to call, if the value doesn't come from a trait impl
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Str>,
+    /// The function signature or the header of the impl block
+    pub signature: Str,
+    /// The number of arguments needed, not counting the receiver of a method
+    pub args: usize,
+    /// Whether the value is wrapped in a `Result` or `Option`
+    pub fallible: bool,
+}
+
+/// Ordered from the most to the least direct way of obtaining a value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstructorKind {
+    /// An associated function of the type, e.g. `Url::parse`
+    Associated,
+    /// A `Default` impl
+    Default,
+    /// A `From`, `TryFrom` or `FromStr` impl
+    Conversion,
+    /// The `build` method of a builder type
+    Builder,
+    /// A free function
+    Function,
+    /// A method of another type, e.g. `Client::get`
+    Method,
+}
+
+/// Returns the ways to obtain a value of the type `item`, simplest first
+///
+/// Constructors with fewer arguments are simpler, and infallible ones are preferred to those
+/// that return a `Result` or `Option`.
+pub fn find(krate: &Crate, item: &Item) -> Vec<Constructor> {
+    let Some(target) = krate.defining_path(item) else {
+        return vec![];
+    };
+    let mut constructors = vec![];
+
+    for function in krate.items.values() {
+        if function.kind != ItemKind::Function || !function.is_public {
+            continue;
+        }
+        let raw = krate.raw(function);
+        let Some(ItemEnum::Function(f)) = raw.index.get(&function.id).map(|info| &info.inner)
+        else {
+            continue;
+        };
+
+        let parent = function
+            .path
+            .rsplit_once("::")
+            .and_then(|(parent, _)| krate.get(parent));
+        let is_associated = parent.is_some_and(|parent| parent.path == item.path);
+
+        let Some(fallible) = f
+            .sig
+            .output
+            .as_ref()
+            .and_then(|output| returns(raw, output, &target, is_associated))
+        else {
+            continue;
+        };
+
+        let has_receiver = f.sig.inputs.first().is_some_and(|(name, _)| name == "self");
+        let kind = match parent.map(|parent| parent.kind) {
+            // methods of the type itself need a value to begin with
+            _ if is_associated && has_receiver => continue,
+            _ if is_associated => ConstructorKind::Associated,
+            // trait methods need a value of an implementing type
+            Some(ItemKind::Trait) => continue,
+            Some(ItemKind::Module) | None => ConstructorKind::Function,
+            _ if &*function.name == "build" => ConstructorKind::Builder,
+            _ => ConstructorKind::Method,
+        };
+
+        constructors.push(Constructor {
+            kind,
+            path: Some(function.path.clone()),
+            signature: function
+                .signature
+                .clone()
+                .unwrap_or_else(|| function.path.clone()),
+            args: f.sig.inputs.len() - has_receiver as usize,
+            fallible,
+        });
+    }
+
+    for impl_ in &krate.impls {
+        // blanket impls like `impl<T> From<T> for T` don't help with getting a value
+        if impl_.for_path.as_deref() != Some(&*target)
+            || matches!(impl_.kind, ImplKind::Blanket | ImplKind::Auto)
+        {
+            continue;
+        }
+        let (kind, args, fallible) =
+            if impls::path_matches(&impl_.trait_path, "std::default::Default") {
+                (ConstructorKind::Default, 0, false)
+            } else if impls::path_matches(&impl_.trait_path, "std::convert::From") {
+                (ConstructorKind::Conversion, 1, false)
+            } else if impls::path_matches(&impl_.trait_path, "std::convert::TryFrom")
+                || impls::is_from_str(&impl_.trait_path)
+            {
+                (ConstructorKind::Conversion, 1, true)
+            } else {
+                continue;
+            };
+
+        constructors.push(Constructor {
+            kind,
+            path: None,
+            signature: impl_.signature.clone(),
+            args,
+            fallible,
+        });
+    }
+
+    constructors.sort_by(|a, b| {
+        (a.args, a.fallible, a.kind, &a.signature).cmp(&(b.args, b.fallible, b.kind, &b.signature))
+    });
+    constructors.dedup_by(|a, b| a.signature == b.signature && a.path == b.path);
+    constructors
+}
+
+/// Returns whether `ty` is the type defined at `target`, possibly wrapped in a `Result` or
+/// `Option`, and if so whether it's wrapped
+///
+/// `Self` is only the target for associated functions of the type.
+fn returns(raw: &rustdoc_types::Crate, ty: &Type, target: &str, is_self: bool) -> Option<bool> {
+    let path = match ty {
+        Type::Generic(name) if name == "Self" => return is_self.then_some(false),
+        Type::ResolvedPath(path) => path,
+        _ => return None,
+    };
+
+    if raw
+        .paths
+        .get(&path.id)
+        .is_some_and(|summary| summary.path.join("::") == target)
+    {
+        return Some(false);
+    }
+
+    let inner = match path.args.as_deref() {
+        Some(GenericArgs::AngleBracketed { args, .. }) => {
+            args.iter().find_map(|arg| match arg {
+                GenericArg::Type(ty) => Some(ty),
+                _ => None,
+            })?
+        }
+        _ => return None,
+    };
+
+    match path.path.rsplit("::").next()? {
+        "Result" | "Option" => returns(raw, inner, target, is_self).map(|_| true),
+        "Box" | "Arc" | "Rc" => returns(raw, inner, target, is_self),
+        _ => None,
+    }
+}
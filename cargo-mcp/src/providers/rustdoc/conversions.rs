@@ -0,0 +1,200 @@
+//! Builds a graph of the conversions between types from `From`, `TryFrom`, `AsRef` and similar
+//! trait impls
+
+use super::{ImplKind, Str, TraitImpl, impls};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// The most conversions to chain together when searching for a path between two types
+const MAX_STEPS: usize = 4;
+
+/// A way to turn a value of one type into another
+#[derive(Clone, Debug, Serialize)]
+pub struct Conversion {
+    pub from: String,
+    pub to: String,
+    /// The implemented trait that provides the conversion, e.g. `TryFrom<&str>`
+    #[serde(rename = "trait")]
+    pub trait_: Str,
+    /// An expression that performs the conversion on `value`
+    pub call: String,
+    /// Whether the conversion returns a `Result`
+    pub fallible: bool,
+    /// Whether either side is a generic parameter of the impl and so stands for many types
+    pub generic: bool,
+    /// The header of the impl block that provides the conversion
+    pub signature: Str,
+}
+
+/// Returns the conversions provided by `impls`
+pub fn collect(impls: &[TraitImpl]) -> Vec<Conversion> {
+    let mut conversions = vec![];
+
+    for impl_ in impls {
+        // blanket impls like `impl<T, U> Into<U> for T` are implied by the impls they're based on
+        if impl_.kind == ImplKind::Blanket {
+            continue;
+        }
+
+        let name = impl_.trait_path.rsplit("::").next().unwrap_or_default();
+        let arg = impl_.args.first().map(|arg| simplify(arg));
+        let for_type = simplify(&impl_.for_type);
+        let base = base_name(&for_type).to_string();
+
+        let (from, to, call, fallible) = match (name, arg) {
+            ("From", Some(arg)) => (arg, for_type, format!("{base}::from(value)"), false),
+            ("Into", Some(arg)) => (for_type, arg, "value.into()".to_string(), false),
+            ("TryFrom", Some(arg)) => (arg, for_type, format!("{base}::try_from(value)"), true),
+            ("TryInto", Some(arg)) => (for_type, arg, "value.try_into()".to_string(), true),
+            ("AsRef", Some(arg)) => (for_type, format!("&{arg}"), "value.as_ref()".into(), false),
+            ("Borrow", Some(arg)) => (for_type, format!("&{arg}"), "value.borrow()".into(), false),
+            ("FromStr", _) if impls::is_from_str(&impl_.trait_path) => (
+                "&str".to_string(),
+                for_type,
+                format!("value.parse::<{base}>()"),
+                true,
+            ),
+            ("IntoIterator", _) => {
+                let item = impl_
+                    .assoc_types
+                    .iter()
+                    .find(|(name, _)| &**name == "Item")
+                    .map_or_else(|| "_".to_string(), |(_, ty)| simplify(ty));
+                (
+                    for_type,
+                    format!("impl Iterator<Item = {item}>"),
+                    "value.into_iter()".to_string(),
+                    false,
+                )
+            }
+            _ => continue,
+        };
+
+        let generic = [&from, &to]
+            .iter()
+            .any(|ty| impl_.type_params.iter().any(|param| mentions(ty, param)));
+
+        conversions.push(Conversion {
+            from,
+            to,
+            trait_: impl_.trait_.clone(),
+            call,
+            fallible,
+            generic,
+            signature: impl_.signature.clone(),
+        });
+    }
+
+    conversions.sort_by(|a, b| (&a.from, &a.to, &a.trait_).cmp(&(&b.from, &b.to, &b.trait_)));
+    conversions.dedup_by(|a, b| a.signature == b.signature);
+    conversions
+}
+
+/// Returns whether `ty` refers to the type parameter `param` anywhere, e.g. `Option<Vec<T>>`
+fn mentions(ty: &str, param: &str) -> bool {
+    ty.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|ident| ident == param)
+}
+
+/// Finds the shortest chain of conversions from one type to another
+///
+/// Conversions involving a generic parameter aren't followed since they depend on the
+/// parameter's bounds.
+pub fn shortest_path<'a>(
+    conversions: &'a [Conversion],
+    from: &str,
+    to: &str,
+) -> Option<Vec<&'a Conversion>> {
+    let mut queue = VecDeque::new();
+    // the conversion used to reach each type along with the number of steps it took
+    let mut visited: HashMap<&str, Option<(usize, usize)>> = HashMap::new();
+
+    let start = simplify(from);
+    for node in conversions
+        .iter()
+        .map(|conversion| conversion.from.as_str())
+        .filter(|node| type_matches(node, from))
+    {
+        if visited.insert(node, None).is_none() {
+            queue.push_back((node, 0));
+        }
+    }
+    if !visited.contains_key(start.as_str()) {
+        visited.insert(&start, None);
+        queue.push_back((&start, 0));
+    }
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if type_matches(node, to) && steps > 0 {
+            let mut path = vec![];
+            let mut current = node;
+            while let Some(Some((idx, _))) = visited.get(current) {
+                let conversion = &conversions[*idx];
+                path.push(conversion);
+                current = &conversion.from;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        if steps == MAX_STEPS {
+            continue;
+        }
+
+        for (idx, conversion) in conversions.iter().enumerate() {
+            if conversion.from != node || conversion.generic {
+                continue;
+            }
+            if !visited.contains_key(conversion.to.as_str()) {
+                visited.insert(&conversion.to, Some((idx, steps + 1)));
+                queue.push_back((&conversion.to, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns whether the type `ty` is the one in `query`
+///
+/// Queries without generic arguments match any instantiation, e.g. `Vec` matches `Vec<u8>`.
+pub fn type_matches(ty: &str, query: &str) -> bool {
+    let query = simplify(query);
+    ty == query || (!query.contains('<') && base_name(ty) == query)
+}
+
+/// Returns the type without its generic arguments
+fn base_name(ty: &str) -> &str {
+    ty.split_once('<').map_or(ty, |(base, _)| base)
+}
+
+/// Normalizes a type so the same type is written the same way, e.g. `&'a http::HeaderValue`
+/// becomes `&HeaderValue`
+///
+/// Module paths and lifetimes are removed since impls refer to types by whatever path is in
+/// scope.
+fn simplify(ty: &str) -> String {
+    let mut out = String::new();
+    let mut chars = ty.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                while out.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    out.pop();
+                }
+            }
+            '\'' => {
+                while chars
+                    .next_if(|c| c.is_alphanumeric() || *c == '_')
+                    .is_some()
+                {}
+                while chars.next_if(|c| *c == ',' || c.is_whitespace()).is_some() {}
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.trim_end().replace("<>", "")
+}
@@ -0,0 +1,171 @@
+//! Records the trait implementations in a crate so they can be looked up by trait or by type
+
+use super::{
+    Str,
+    signature::{self, Render},
+};
+use rustdoc_types::{GenericArg, GenericArgs, GenericParamDefKind, ItemEnum, Type};
+use serde::Serialize;
+
+/// A trait implemented for a type
+#[derive(Clone, Debug, Serialize)]
+pub struct TraitImpl {
+    /// The implemented trait, e.g. `Service<Request>`
+    #[serde(rename = "trait")]
+    pub trait_: Str,
+    /// The type the trait is implemented for, e.g. `Vec<T>`
+    pub for_type: Str,
+    pub kind: ImplKind,
+    /// The header of the impl block, e.g. `impl<T: Clone> Clone for Wrapper<T>`
+    pub signature: Str,
+    /// The path the trait is defined at, e.g. `core::error::Error`
+    #[serde(skip)]
+    pub trait_path: Str,
+    /// The path the type is defined at, if it's a named type
+    #[serde(skip)]
+    pub for_path: Option<Str>,
+    /// The generic type arguments of the trait, e.g. `Request` in `Service<Request>`
+    #[serde(skip)]
+    pub args: Vec<Str>,
+    /// The associated types defined by the impl, e.g. `("Item", "u8")`
+    #[serde(skip)]
+    pub assoc_types: Vec<(Str, Str)>,
+    /// The names of the impl's generic type parameters
+    #[serde(skip)]
+    pub type_params: Vec<Str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImplKind {
+    /// An impl for a specific type
+    Direct,
+    /// An impl for a type with generic parameters, e.g. `impl<T> Trait for Wrapper<T>`
+    Generic,
+    /// An impl for any type that meets some bounds, e.g. `impl<T: Display> ToString for T`
+    Blanket,
+    /// An auto trait impl that the compiler derived, e.g. `Send`
+    Auto,
+}
+
+/// Collects every trait impl in the rustdoc output, including synthetic and blanket impls
+pub fn collect(krate: &rustdoc_types::Crate) -> Vec<TraitImpl> {
+    let mut impls = vec![];
+
+    for info in krate.index.values() {
+        let ItemEnum::Impl(impl_) = &info.inner else {
+            continue;
+        };
+        let Some(trait_) = &impl_.trait_ else {
+            continue;
+        };
+
+        let kind = if impl_.is_synthetic {
+            ImplKind::Auto
+        } else if impl_.blanket_impl.is_some() || matches!(impl_.for_, Type::Generic(_)) {
+            ImplKind::Blanket
+        } else if impl_
+            .generics
+            .params
+            .iter()
+            .any(|param| !matches!(param.kind, GenericParamDefKind::Lifetime { .. }))
+        {
+            ImplKind::Generic
+        } else {
+            ImplKind::Direct
+        };
+
+        let trait_path = match krate.paths.get(&trait_.id) {
+            Some(summary) => summary.path.join("::"),
+            None => trait_.path.clone(),
+        };
+        let for_path = match &impl_.for_ {
+            Type::ResolvedPath(path) => Some(match krate.paths.get(&path.id) {
+                Some(summary) => summary.path.join("::").into(),
+                None => path.path.as_str().into(),
+            }),
+            _ => None,
+        };
+
+        let args = match trait_.args.as_deref() {
+            Some(GenericArgs::AngleBracketed { args, .. }) => args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(ty) => Some(ty.to_source().into()),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let assoc_types = impl_
+            .items
+            .iter()
+            .filter_map(|id| {
+                let info = krate.index.get(id)?;
+                let ItemEnum::AssocType {
+                    type_: Some(ty), ..
+                } = &info.inner
+                else {
+                    return None;
+                };
+                Some((info.name.as_deref()?.into(), ty.to_source().into()))
+            })
+            .collect();
+
+        let type_params = impl_
+            .generics
+            .params
+            .iter()
+            .filter(|param| matches!(param.kind, GenericParamDefKind::Type { .. }))
+            .map(|param| param.name.as_str().into())
+            .collect();
+
+        impls.push(TraitImpl {
+            trait_: signature::trait_impl(impl_, krate)
+                .unwrap_or_else(|| trait_.path.clone())
+                .into(),
+            for_type: impl_.for_.to_source().into(),
+            kind,
+            signature: signature::item(info)
+                .unwrap_or_else(|| impl_.to_source())
+                .into(),
+            trait_path: trait_path.into(),
+            for_path,
+            args,
+            assoc_types,
+            type_params,
+        });
+    }
+
+    impls
+}
+
+/// Returns whether `path` refers to the same item as `query`
+///
+/// The standard library crates are treated as one, so `std::error::Error` matches
+/// `core::error::Error`. Queries without a module only need to match the name.
+pub fn path_matches(path: &str, query: &str) -> bool {
+    if !query.contains("::") {
+        return path.rsplit("::").next() == Some(query);
+    }
+    normalize(path) == normalize(query)
+}
+
+/// Returns whether `path` is the standard library's `FromStr`, which is defined in a private
+/// module of `core::str`
+pub fn is_from_str(path: &str) -> bool {
+    ["std::str::FromStr", "std::str::traits::FromStr"]
+        .iter()
+        .any(|std| path_matches(path, std))
+}
+
+/// Refers to items of `core` and `alloc` by their `std` path
+pub fn normalize(path: &str) -> String {
+    let path = path.trim_start_matches("::");
+    for krate in ["core::", "alloc::"] {
+        if let Some(rest) = path.strip_prefix(krate) {
+            return format!("std::{rest}");
+        }
+    }
+    path.to_string()
+}
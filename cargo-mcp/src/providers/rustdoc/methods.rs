@@ -0,0 +1,363 @@
+//! Resolves the methods that can be called on a value, following `Deref` impls like the
+//! compiler's method lookup does
+
+use super::{Crate, Str, impls, signature};
+use rustdoc_types::{GenericArgs, Id, Impl, ItemEnum, Type};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// The most `Deref` targets to follow, in case of cycles
+const MAX_DEREFS: usize = 8;
+
+/// Traits in the standard prelude, which don't need to be imported to call their methods
+const PRELUDE: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Clone",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+];
+
+/// Standard library types that implement `Deref`, along with their target
+///
+/// `T` stands for the first generic argument of the type.
+const STD_DEREFS: &[(&str, &str)] = &[
+    ("Arc", "T"),
+    ("Box", "T"),
+    ("CString", "CStr"),
+    ("Cow", "T"),
+    ("ManuallyDrop", "T"),
+    ("MutexGuard", "T"),
+    ("OsString", "OsStr"),
+    ("PathBuf", "Path"),
+    ("Pin", "T"),
+    ("Rc", "T"),
+    ("Ref", "T"),
+    ("RefMut", "T"),
+    ("RwLockReadGuard", "T"),
+    ("RwLockWriteGuard", "T"),
+    ("String", "str"),
+    ("Vec", "[T]"),
+];
+
+/// The methods that can be called on a value of a type
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MethodSet {
+    /// The type followed by each of the `Deref` targets that were followed
+    pub deref_chain: Vec<String>,
+    pub methods: Vec<Method>,
+    /// Types in the chain that aren't documented in the crate, so their own methods are missing
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub undocumented: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Method {
+    pub name: Str,
+    /// The type the method is defined on, which is reached after `deref` dereferences
+    pub self_type: String,
+    pub deref: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Str>,
+    /// The trait that provides the method
+    #[serde(rename = "trait", skip_serializing_if = "Option::is_none")]
+    pub trait_: Option<Str>,
+    /// The path to import for the method to be in scope, if the trait isn't in the prelude
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<Str>,
+}
+
+/// Returns the methods that can be called on `ty`, e.g. `Arc<my_crate::Wrapper<u8>>`
+///
+/// The standard library isn't documented, so its types are followed through [`STD_DEREFS`]
+/// without listing their own methods.
+///
+/// Each name is listed once, for the method that method-call syntax picks: the first along the
+/// `Deref` chain, preferring inherent methods over trait methods. The others can only be called
+/// by dereferencing explicitly or with a qualified path.
+pub fn resolve(krate: &Crate, ty: &str) -> MethodSet {
+    let mut set = MethodSet::default();
+    let mut shadowed = HashSet::new();
+    let mut ty = strip_refs(ty).to_string();
+
+    for deref in 0..=MAX_DEREFS {
+        if set.deref_chain.contains(&ty) {
+            break;
+        }
+        set.deref_chain.push(ty.clone());
+
+        let (base, args) = split_args(&ty);
+        let Some(item) = krate.get(base).filter(|item| {
+            matches!(
+                item.kind,
+                rustdoc_types::ItemKind::Struct
+                    | rustdoc_types::ItemKind::Enum
+                    | rustdoc_types::ItemKind::Union
+            )
+        }) else {
+            set.undocumented.push(ty.clone());
+            let name = base.rsplit("::").next().unwrap_or(base);
+            match STD_DEREFS.iter().find(|(std, _)| *std == name) {
+                Some((_, target)) => {
+                    let arg = args.first().copied().unwrap_or("_");
+                    ty = strip_refs(&substitute(target, &HashMap::from([("T", arg)]))).to_string();
+                    continue;
+                }
+                None => break,
+            }
+        };
+
+        let raw = krate.raw(item);
+        let impl_ids = match raw.index.get(&item.id).map(|info| &info.inner) {
+            Some(ItemEnum::Struct(s)) => &s.impls[..],
+            Some(ItemEnum::Union(u)) => &u.impls[..],
+            Some(ItemEnum::Enum(e)) => &e.impls[..],
+            _ => &[],
+        };
+
+        let mut methods = vec![];
+        let mut target = None;
+
+        for impl_id in impl_ids {
+            let Some(ItemEnum::Impl(impl_)) = raw.index.get(impl_id).map(|info| &info.inner) else {
+                continue;
+            };
+            if impl_.is_synthetic || impl_.is_negative {
+                continue;
+            }
+
+            let Some(trait_) = &impl_.trait_ else {
+                for (name, info) in functions(raw, &impl_.items) {
+                    if matches!(info.visibility, rustdoc_types::Visibility::Public) {
+                        methods.push((name, signature::item(info), None));
+                    }
+                }
+                continue;
+            };
+
+            let trait_path = match raw.paths.get(&trait_.id) {
+                Some(summary) => summary.path.join("::"),
+                None => trait_.path.clone(),
+            };
+            let trait_name: Str = trait_path.rsplit("::").next().unwrap_or_default().into();
+
+            // `Deref` is defined in the private `core::ops::deref` module, so match on the name
+            if impls::path_matches(&trait_path, "Deref") {
+                target = deref_target(raw, impl_, &args);
+            }
+
+            let mut found: Vec<_> = functions(raw, &impl_.items)
+                .map(|(name, info)| (name, signature::item(info)))
+                .collect();
+            // methods with a default that the impl doesn't override are declared by the trait
+            let declared = match raw.index.get(&trait_.id).map(|info| &info.inner) {
+                Some(ItemEnum::Trait(t)) => functions(raw, &t.items).collect(),
+                _ => vec![],
+            };
+            for name in &impl_.provided_trait_methods {
+                let signature = declared
+                    .iter()
+                    .find(|(declared, _)| declared == name)
+                    .and_then(|(_, info)| signature::item(info));
+                found.push((name.as_str(), signature));
+            }
+
+            let import = import_path(krate, &trait_path);
+            for (name, signature) in found {
+                methods.push((name, signature, Some((trait_name.clone(), import.clone()))));
+            }
+        }
+
+        // inherent methods take priority over trait methods with the same name
+        methods.sort_by_key(|(_, _, trait_)| trait_.is_some());
+        for (name, signature, trait_) in methods {
+            // the compiler picks the first method along the chain with a matching name, and impls
+            // of a trait with different arguments, like `AsRef<str>` and `AsRef<[u8]>`, provide
+            // the same method
+            if !shadowed.insert(name) {
+                continue;
+            }
+            let (trait_, import) = trait_.unzip();
+            set.methods.push(Method {
+                name: name.into(),
+                self_type: ty.clone(),
+                deref,
+                signature: signature.map(Str::from),
+                trait_,
+                import: import.flatten(),
+            });
+        }
+        match target {
+            Some(target) => ty = target,
+            None => break,
+        }
+    }
+
+    set
+}
+
+/// Returns the functions that take `self` among `items`, along with their names
+fn functions<'a>(
+    raw: &'a rustdoc_types::Crate,
+    items: &'a [Id],
+) -> impl Iterator<Item = (&'a str, &'a rustdoc_types::Item)> + 'a {
+    items.iter().filter_map(|id| {
+        let info = raw.index.get(id)?;
+        let ItemEnum::Function(function) = &info.inner else {
+            return None;
+        };
+        function
+            .sig
+            .inputs
+            .first()
+            .is_some_and(|(name, _)| name == "self")
+            .then_some((info.name.as_deref()?, info))
+    })
+}
+
+/// Returns the `Target` of a `Deref` impl with the type's generic arguments filled in
+fn deref_target(raw: &rustdoc_types::Crate, impl_: &Impl, args: &[&str]) -> Option<String> {
+    let target = impl_.items.iter().find_map(|id| {
+        let info = raw.index.get(id)?;
+        match &info.inner {
+            ItemEnum::AssocType {
+                type_: Some(ty), ..
+            } if info.name.as_deref() == Some("Target") => Some(ty),
+            _ => None,
+        }
+    })?;
+
+    // map the impl's parameters to the arguments in the query, e.g. `T` in `Wrapper<T>`
+    let mut params = HashMap::new();
+    if let Type::ResolvedPath(path) = &impl_.for_
+        && let Some(GenericArgs::AngleBracketed { args: for_args, .. }) = path.args.as_deref()
+    {
+        let for_args = for_args.iter().filter_map(|arg| match arg {
+            rustdoc_types::GenericArg::Type(Type::Generic(name)) => Some(Some(name.as_str())),
+            rustdoc_types::GenericArg::Type(_) => Some(None),
+            _ => None,
+        });
+        for (param, arg) in for_args.zip(args) {
+            if let Some(param) = param {
+                params.insert(param, *arg);
+            }
+        }
+    }
+
+    // refer to named types by the path they're defined at so they can be looked up
+    let rendered = match target {
+        Type::ResolvedPath(path) => {
+            let mut out = match raw.paths.get(&path.id) {
+                Some(summary) => summary.path.join("::"),
+                None => path.path.clone(),
+            };
+            if let Some(args) = &path.args {
+                signature::Render::render(&**args, &mut out);
+            }
+            out
+        }
+        ty => signature::Render::to_source(ty),
+    };
+
+    Some(strip_refs(&substitute(&rendered, &params)).to_string())
+}
+
+/// Returns the path to import a trait from, or `None` if it's in the prelude
+fn import_path(krate: &Crate, trait_path: &str) -> Option<Str> {
+    if let Some(item) = krate.get(trait_path) {
+        return Some(item.path.clone());
+    }
+    let name = trait_path.rsplit("::").next().unwrap_or(trait_path);
+    let path = impls::normalize(trait_path);
+    if PRELUDE.contains(&name) && path.starts_with("std::") {
+        return None;
+    }
+
+    // standard library traits are usually defined in private modules and re-exported one level
+    // down, e.g. `core::ops::deref::Deref` is imported from `std::ops::Deref`
+    let segments: Vec<_> = path.split("::").collect();
+    if segments.len() > 3 && segments[0] == "std" && segments[1] != "os" {
+        return Some(format!("std::{}::{name}", segments[1]).into());
+    }
+    Some(path.into())
+}
+
+/// Splits a type into its path and top-level generic arguments, e.g. `Arc<Mutex<T>>` into
+/// `Arc` and `[Mutex<T>]`
+fn split_args(ty: &str) -> (&str, Vec<&str>) {
+    let Some((base, rest)) = ty.split_once('<') else {
+        return (ty.trim(), vec![]);
+    };
+    let rest = rest.trim_end().strip_suffix('>').unwrap_or(rest);
+
+    let mut args = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, c) in rest.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(rest[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(rest[start..].trim());
+    args.retain(|arg| !arg.is_empty() && !arg.starts_with('\''));
+
+    (base.trim(), args)
+}
+
+/// Replaces whole identifiers in `ty` using `params`
+fn substitute(ty: &str, params: &HashMap<&str, &str>) -> String {
+    let mut out = String::new();
+    let mut ident = String::new();
+    for c in ty.chars().chain(['\0']) {
+        if c.is_alphanumeric() || c == '_' {
+            ident.push(c);
+            continue;
+        }
+        out.push_str(params.get(ident.as_str()).copied().unwrap_or(&ident));
+        ident.clear();
+        if c != '\0' {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Removes any leading references, since methods are looked up on the referenced type
+fn strip_refs(ty: &str) -> &str {
+    let mut ty = ty.trim();
+    while let Some(rest) = ty.strip_prefix('&') {
+        ty = rest.trim_start();
+        if ty.starts_with('\'') {
+            ty = ty
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, rest)| rest.trim_start());
+        }
+        ty = ty.strip_prefix("mut ").unwrap_or(ty).trim_start();
+    }
+    ty
+}
@@ -0,0 +1,161 @@
+//! Resolves `pub use` re-exports to find every public path of an item
+
+use rustdoc_types::{Id, ItemEnum, Use, Visibility};
+use std::collections::HashMap;
+
+/// Limits how deep glob and module re-exports are followed
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Default)]
+pub struct Reexports {
+    /// Every public path of a local item, shortest first
+    pub local: HashMap<Id, Vec<String>>,
+    /// Re-exports of items that are defined in other crates
+    pub external: Vec<External>,
+}
+
+#[derive(Debug)]
+pub struct External {
+    /// The name of the crate that defines the item
+    pub crate_name: String,
+    /// The path of the item in the defining crate
+    pub source: String,
+    /// The path the item is re-exported at
+    pub alias: String,
+    /// Whether the contents of `source` are re-exported rather than `source` itself
+    pub is_glob: bool,
+}
+
+impl Reexports {
+    pub fn new(krate: &rustdoc_types::Crate, crate_name: &str) -> Self {
+        let mut reexports = Self::default();
+        let Some(root) = krate.index.get(&krate.root) else {
+            return reexports;
+        };
+
+        let mut walker = Walker {
+            krate,
+            crate_id: root.crate_id,
+            stack: vec![],
+            reexports: &mut reexports,
+        };
+        walker.module(krate.root, crate_name);
+
+        for (id, paths) in reexports.local.iter_mut() {
+            let name = krate.index.get(id).and_then(|info| info.name.as_deref());
+            sort_paths(paths, name.unwrap_or_default());
+        }
+
+        reexports
+    }
+}
+
+/// Orders paths by the number of segments so the shortest public path comes first
+///
+/// Paths of the same length that keep the item's `name` are preferred over renamed ones.
+pub fn sort_paths<T: AsRef<str>>(paths: &mut Vec<T>, name: &str) {
+    let key = |path: &str| {
+        let is_renamed = path.rsplit("::").next() != Some(name);
+        (path.matches("::").count(), is_renamed)
+    };
+    paths.sort_by(|a, b| {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        key(a).cmp(&key(b)).then_with(|| a.cmp(b))
+    });
+    paths.dedup_by(|a, b| a.as_ref() == b.as_ref());
+}
+
+struct Walker<'a> {
+    krate: &'a rustdoc_types::Crate,
+    crate_id: u32,
+    /// The modules currently being walked, used to break glob cycles
+    stack: Vec<Id>,
+    reexports: &'a mut Reexports,
+}
+
+impl Walker<'_> {
+    fn module(&mut self, id: Id, prefix: &str) {
+        if self.stack.contains(&id) || self.stack.len() >= MAX_DEPTH {
+            return;
+        }
+
+        let Some(ItemEnum::Module(module)) = self.krate.index.get(&id).map(|info| &info.inner)
+        else {
+            return;
+        };
+
+        self.stack.push(id);
+
+        for child in &module.items {
+            let Some(info) = self.krate.index.get(child) else {
+                continue;
+            };
+
+            if !matches!(info.visibility, Visibility::Public) {
+                continue;
+            }
+
+            if let ItemEnum::Use(use_) = &info.inner {
+                self.use_(use_, prefix);
+                continue;
+            }
+
+            let Some(name) = info.name.as_ref() else {
+                continue;
+            };
+
+            let path = format!("{prefix}::{name}");
+            if let ItemEnum::Module(_) = &info.inner {
+                self.module(*child, &path);
+            }
+            self.push(*child, path);
+        }
+
+        self.stack.pop();
+    }
+
+    fn use_(&mut self, use_: &Use, prefix: &str) {
+        // primitive re-exports don't have an id
+        let Some(target) = use_.id else {
+            return;
+        };
+
+        let path = if use_.is_glob {
+            prefix.to_string()
+        } else {
+            format!("{prefix}::{}", use_.name)
+        };
+
+        match self.krate.index.get(&target) {
+            Some(info) if info.crate_id == self.crate_id => match &info.inner {
+                ItemEnum::Module(_) => {
+                    self.module(target, &path);
+                    if !use_.is_glob {
+                        self.push(target, path);
+                    }
+                }
+                // glob imports of enum variants aren't tracked
+                _ if use_.is_glob => {}
+                _ => self.push(target, path),
+            },
+            _ => {
+                let Some(summary) = self.krate.paths.get(&target) else {
+                    return;
+                };
+                let Some(external) = self.krate.external_crates.get(&summary.crate_id) else {
+                    return;
+                };
+                self.reexports.external.push(External {
+                    crate_name: external.name.clone(),
+                    source: summary.path.join("::"),
+                    alias: path,
+                    is_glob: use_.is_glob,
+                });
+            }
+        }
+    }
+
+    fn push(&mut self, id: Id, path: String) {
+        self.reexports.local.entry(id).or_default().push(path);
+    }
+}
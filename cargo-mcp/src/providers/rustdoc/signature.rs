@@ -0,0 +1,668 @@
+//! Renders rustdoc JSON types back into readable Rust syntax
+
+use rustdoc_types::{
+    Abi, AssocItemConstraint, AssocItemConstraintKind, Function, FunctionHeader, FunctionPointer,
+    GenericArg, GenericArgs, GenericBound, GenericParamDef, GenericParamDefKind, Generics, Impl,
+    Item, ItemEnum, Path, PolyTrait, PreciseCapturingArg, Term, TraitBoundModifier, Type,
+    Visibility, WherePredicate,
+};
+
+pub trait Render {
+    fn render(&self, out: &mut String);
+
+    fn to_source(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out);
+        out
+    }
+}
+
+/// Renders the declaration of an item, e.g. `pub fn new(field: String) -> Self`
+///
+/// Returns `None` for items that don't have a meaningful signature, like modules.
+pub fn item(item: &Item) -> Option<String> {
+    let mut out = String::new();
+    let name = item.name.as_deref().unwrap_or_default();
+
+    match &item.inner {
+        ItemEnum::Function(function) => {
+            item.visibility.render(&mut out);
+            function_decl(&mut out, name, function);
+        }
+        ItemEnum::StructField(ty) => {
+            item.visibility.render(&mut out);
+            out.push_str(name);
+            out.push_str(": ");
+            ty.render(&mut out);
+        }
+        ItemEnum::Struct(s) => {
+            item.visibility.render(&mut out);
+            type_decl(&mut out, "struct", name, &s.generics);
+        }
+        ItemEnum::Enum(e) => {
+            item.visibility.render(&mut out);
+            type_decl(&mut out, "enum", name, &e.generics);
+        }
+        ItemEnum::Union(u) => {
+            item.visibility.render(&mut out);
+            type_decl(&mut out, "union", name, &u.generics);
+        }
+        ItemEnum::Trait(t) => {
+            item.visibility.render(&mut out);
+            if t.is_unsafe {
+                out.push_str("unsafe ");
+            }
+            if t.is_auto {
+                out.push_str("auto ");
+            }
+            out.push_str("trait ");
+            out.push_str(name);
+            generic_params(&mut out, &t.generics);
+            if !t.bounds.is_empty() {
+                out.push_str(": ");
+                join(&mut out, &t.bounds, " + ");
+            }
+            where_clause(&mut out, &t.generics);
+        }
+        ItemEnum::TypeAlias(alias) => {
+            item.visibility.render(&mut out);
+            out.push_str("type ");
+            out.push_str(name);
+            generic_params(&mut out, &alias.generics);
+            out.push_str(" = ");
+            alias.type_.render(&mut out);
+            where_clause(&mut out, &alias.generics);
+        }
+        ItemEnum::Constant { type_, const_ } => {
+            item.visibility.render(&mut out);
+            out.push_str("const ");
+            out.push_str(name);
+            out.push_str(": ");
+            type_.render(&mut out);
+            out.push_str(" = ");
+            out.push_str(&const_.expr);
+        }
+        ItemEnum::Static(s) => {
+            item.visibility.render(&mut out);
+            if s.is_unsafe {
+                out.push_str("unsafe ");
+            }
+            out.push_str(if s.is_mutable {
+                "static mut "
+            } else {
+                "static "
+            });
+            out.push_str(name);
+            out.push_str(": ");
+            s.type_.render(&mut out);
+        }
+        ItemEnum::AssocConst { type_, value } => {
+            item.visibility.render(&mut out);
+            out.push_str("const ");
+            out.push_str(name);
+            out.push_str(": ");
+            type_.render(&mut out);
+            if let Some(value) = value {
+                out.push_str(" = ");
+                out.push_str(value);
+            }
+        }
+        ItemEnum::AssocType {
+            generics,
+            bounds,
+            type_,
+        } => {
+            item.visibility.render(&mut out);
+            out.push_str("type ");
+            out.push_str(name);
+            generic_params(&mut out, generics);
+            if !bounds.is_empty() {
+                out.push_str(": ");
+                join(&mut out, bounds, " + ");
+            }
+            where_clause(&mut out, generics);
+            if let Some(ty) = type_ {
+                out.push_str(" = ");
+                ty.render(&mut out);
+            }
+        }
+        ItemEnum::Impl(impl_) => impl_.render(&mut out),
+        _ => return None,
+    }
+
+    Some(out)
+}
+
+/// Renders the implemented trait of an impl block, e.g. `From<&str>` or `Iterator<Item = u8>`
+///
+/// Associated types assigned in the impl are folded into the trait's generic arguments and
+/// negative impls are prefixed with `!`.
+pub fn trait_impl(impl_: &Impl, krate: &rustdoc_types::Crate) -> Option<String> {
+    let trait_ = impl_.trait_.as_ref()?;
+
+    let bindings = impl_.items.iter().filter_map(|id| {
+        let info = krate.index.get(id)?;
+        let ItemEnum::AssocType {
+            type_: Some(ty), ..
+        } = &info.inner
+        else {
+            return None;
+        };
+        Some(AssocItemConstraint {
+            name: info.name.clone()?,
+            args: None,
+            binding: AssocItemConstraintKind::Equality(Term::Type(ty.clone())),
+        })
+    });
+
+    let mut trait_ = trait_.clone();
+    let mut bindings = bindings.peekable();
+    if bindings.peek().is_some() {
+        let args = trait_.args.get_or_insert_with(|| {
+            Box::new(GenericArgs::AngleBracketed {
+                args: vec![],
+                constraints: vec![],
+            })
+        });
+        if let GenericArgs::AngleBracketed { constraints, .. } = &mut **args {
+            constraints.extend(bindings);
+        }
+    }
+
+    let mut out = String::new();
+    if impl_.is_negative {
+        out.push('!');
+    }
+    trait_.render(&mut out);
+    Some(out)
+}
+
+pub fn function_decl(out: &mut String, name: &str, function: &Function) {
+    function.header.render(out);
+    out.push_str("fn ");
+    out.push_str(name);
+    generic_params(out, &function.generics);
+    out.push('(');
+    for (idx, (name, ty)) in function.sig.inputs.iter().enumerate() {
+        if idx > 0 {
+            out.push_str(", ");
+        }
+        if name == "self" {
+            self_param(out, ty);
+        } else {
+            out.push_str(name);
+            out.push_str(": ");
+            ty.render(out);
+        }
+    }
+    if function.sig.is_c_variadic {
+        out.push_str(", ...");
+    }
+    out.push(')');
+    if let Some(output) = &function.sig.output {
+        out.push_str(" -> ");
+        output.render(out);
+    }
+    where_clause(out, &function.generics);
+}
+
+/// Writes `self`, `&self` and `&mut self` in their shorthand forms
+fn self_param(out: &mut String, ty: &Type) {
+    match ty {
+        Type::Generic(name) if name == "Self" => out.push_str("self"),
+        Type::BorrowedRef {
+            lifetime,
+            is_mutable,
+            type_,
+        } if matches!(&**type_, Type::Generic(name) if name == "Self") => {
+            out.push('&');
+            if let Some(lifetime) = lifetime {
+                out.push_str(lifetime);
+                out.push(' ');
+            }
+            if *is_mutable {
+                out.push_str("mut ");
+            }
+            out.push_str("self");
+        }
+        ty => {
+            out.push_str("self: ");
+            ty.render(out);
+        }
+    }
+}
+
+fn type_decl(out: &mut String, keyword: &str, name: &str, generics: &Generics) {
+    out.push_str(keyword);
+    out.push(' ');
+    out.push_str(name);
+    generic_params(out, generics);
+    where_clause(out, generics);
+}
+
+/// Writes the `<T: Bound>` list of a declaration, omitting compiler-generated `impl Trait` params
+pub fn generic_params(out: &mut String, generics: &Generics) {
+    let mut params = generics.params.iter().filter(|param| {
+        !matches!(
+            param.kind,
+            GenericParamDefKind::Type {
+                is_synthetic: true,
+                ..
+            }
+        )
+    });
+
+    let Some(first) = params.next() else {
+        return;
+    };
+    out.push('<');
+    first.render(out);
+    for param in params {
+        out.push_str(", ");
+        param.render(out);
+    }
+    out.push('>');
+}
+
+pub fn where_clause(out: &mut String, generics: &Generics) {
+    if generics.where_predicates.is_empty() {
+        return;
+    }
+    out.push_str(" where ");
+    join(out, &generics.where_predicates, ", ");
+}
+
+impl<T: Render + ?Sized> Render for Box<T> {
+    fn render(&self, out: &mut String) {
+        (**self).render(out)
+    }
+}
+
+pub fn join<T: Render>(out: &mut String, items: &[T], sep: &str) {
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            out.push_str(sep);
+        }
+        item.render(out);
+    }
+}
+
+impl Render for Type {
+    fn render(&self, out: &mut String) {
+        match self {
+            Type::ResolvedPath(path) => path.render(out),
+            Type::DynTrait(dyn_trait) => {
+                out.push_str("dyn ");
+                join(out, &dyn_trait.traits, " + ");
+                if let Some(lifetime) = &dyn_trait.lifetime {
+                    out.push_str(" + ");
+                    out.push_str(lifetime);
+                }
+            }
+            Type::Generic(name) | Type::Primitive(name) => out.push_str(name),
+            Type::FunctionPointer(f) => f.render(out),
+            Type::Tuple(types) => {
+                out.push('(');
+                join(out, types, ", ");
+                if types.len() == 1 {
+                    out.push(',');
+                }
+                out.push(')');
+            }
+            Type::Slice(ty) => {
+                out.push('[');
+                ty.render(out);
+                out.push(']');
+            }
+            Type::Array { type_, len } => {
+                out.push('[');
+                type_.render(out);
+                out.push_str("; ");
+                out.push_str(len);
+                out.push(']');
+            }
+            Type::Pat { type_, .. } => type_.render(out),
+            Type::ImplTrait(bounds) => {
+                out.push_str("impl ");
+                join(out, bounds, " + ");
+            }
+            Type::Infer => out.push('_'),
+            Type::RawPointer { is_mutable, type_ } => {
+                out.push_str(if *is_mutable { "*mut " } else { "*const " });
+                type_.render(out);
+            }
+            Type::BorrowedRef {
+                lifetime,
+                is_mutable,
+                type_,
+            } => {
+                out.push('&');
+                if let Some(lifetime) = lifetime {
+                    out.push_str(lifetime);
+                    out.push(' ');
+                }
+                if *is_mutable {
+                    out.push_str("mut ");
+                }
+                type_.render(out);
+            }
+            Type::QualifiedPath {
+                name,
+                args,
+                self_type,
+                trait_,
+            } => {
+                match (trait_, &**self_type) {
+                    // `T::Item` reads better than `<T as Iterator>::Item` for generic params
+                    (Some(_), Type::Generic(_)) | (None, _) => self_type.render(out),
+                    (Some(trait_), self_type) => {
+                        out.push('<');
+                        self_type.render(out);
+                        out.push_str(" as ");
+                        trait_.render(out);
+                        out.push('>');
+                    }
+                }
+                out.push_str("::");
+                out.push_str(name);
+                if let Some(args) = args {
+                    args.render(out);
+                }
+            }
+        }
+    }
+}
+
+impl Render for Path {
+    fn render(&self, out: &mut String) {
+        out.push_str(&self.path);
+        if let Some(args) = &self.args {
+            args.render(out);
+        }
+    }
+}
+
+impl Render for GenericArgs {
+    fn render(&self, out: &mut String) {
+        match self {
+            GenericArgs::AngleBracketed { args, constraints } => {
+                if args.is_empty() && constraints.is_empty() {
+                    return;
+                }
+                out.push('<');
+                join(out, args, ", ");
+                if !args.is_empty() && !constraints.is_empty() {
+                    out.push_str(", ");
+                }
+                join(out, constraints, ", ");
+                out.push('>');
+            }
+            GenericArgs::Parenthesized { inputs, output } => {
+                out.push('(');
+                join(out, inputs, ", ");
+                out.push(')');
+                if let Some(output) = output {
+                    out.push_str(" -> ");
+                    output.render(out);
+                }
+            }
+            GenericArgs::ReturnTypeNotation => out.push_str("(..)"),
+        }
+    }
+}
+
+impl Render for GenericArg {
+    fn render(&self, out: &mut String) {
+        match self {
+            GenericArg::Lifetime(lifetime) => out.push_str(lifetime),
+            GenericArg::Type(ty) => ty.render(out),
+            GenericArg::Const(constant) => out.push_str(&constant.expr),
+            GenericArg::Infer => out.push('_'),
+        }
+    }
+}
+
+impl Render for AssocItemConstraint {
+    fn render(&self, out: &mut String) {
+        out.push_str(&self.name);
+        if let Some(args) = &self.args {
+            args.render(out);
+        }
+        match &self.binding {
+            AssocItemConstraintKind::Equality(term) => {
+                out.push_str(" = ");
+                term.render(out);
+            }
+            AssocItemConstraintKind::Constraint(bounds) => {
+                out.push_str(": ");
+                join(out, bounds, " + ");
+            }
+        }
+    }
+}
+
+impl Render for Term {
+    fn render(&self, out: &mut String) {
+        match self {
+            Term::Type(ty) => ty.render(out),
+            Term::Constant(constant) => out.push_str(&constant.expr),
+        }
+    }
+}
+
+impl Render for GenericBound {
+    fn render(&self, out: &mut String) {
+        match self {
+            GenericBound::TraitBound {
+                trait_,
+                generic_params,
+                modifier,
+            } => {
+                higher_ranked(out, generic_params);
+                match modifier {
+                    TraitBoundModifier::None => {}
+                    TraitBoundModifier::Maybe => out.push('?'),
+                    TraitBoundModifier::MaybeConst => out.push_str("~const "),
+                }
+                trait_.render(out);
+            }
+            GenericBound::Outlives(lifetime) => out.push_str(lifetime),
+            GenericBound::Use(args) => {
+                out.push_str("use<");
+                join(out, args, ", ");
+                out.push('>');
+            }
+        }
+    }
+}
+
+impl Render for PreciseCapturingArg {
+    fn render(&self, out: &mut String) {
+        match self {
+            PreciseCapturingArg::Lifetime(name) | PreciseCapturingArg::Param(name) => {
+                out.push_str(name)
+            }
+        }
+    }
+}
+
+impl Render for PolyTrait {
+    fn render(&self, out: &mut String) {
+        higher_ranked(out, &self.generic_params);
+        self.trait_.render(out);
+    }
+}
+
+impl Render for FunctionPointer {
+    fn render(&self, out: &mut String) {
+        higher_ranked(out, &self.generic_params);
+        self.header.render(out);
+        out.push_str("fn(");
+        for (idx, (_name, ty)) in self.sig.inputs.iter().enumerate() {
+            if idx > 0 {
+                out.push_str(", ");
+            }
+            ty.render(out);
+        }
+        if self.sig.is_c_variadic {
+            out.push_str(", ...");
+        }
+        out.push(')');
+        if let Some(output) = &self.sig.output {
+            out.push_str(" -> ");
+            output.render(out);
+        }
+    }
+}
+
+impl Render for FunctionHeader {
+    fn render(&self, out: &mut String) {
+        if self.is_const {
+            out.push_str("const ");
+        }
+        if self.is_async {
+            out.push_str("async ");
+        }
+        if self.is_unsafe {
+            out.push_str("unsafe ");
+        }
+        self.abi.render(out);
+    }
+}
+
+impl Render for Abi {
+    fn render(&self, out: &mut String) {
+        let (name, unwind) = match self {
+            Abi::Rust => return,
+            Abi::C { unwind } => ("C", unwind),
+            Abi::Cdecl { unwind } => ("cdecl", unwind),
+            Abi::Stdcall { unwind } => ("stdcall", unwind),
+            Abi::Fastcall { unwind } => ("fastcall", unwind),
+            Abi::Aapcs { unwind } => ("aapcs", unwind),
+            Abi::Win64 { unwind } => ("win64", unwind),
+            Abi::SysV64 { unwind } => ("sysv64", unwind),
+            Abi::System { unwind } => ("system", unwind),
+            Abi::Other(name) => (name.as_str(), &false),
+        };
+        out.push_str("extern \"");
+        out.push_str(name);
+        if *unwind {
+            out.push_str("-unwind");
+        }
+        out.push_str("\" ");
+    }
+}
+
+/// Writes the `for<'a>` prefix of a higher-ranked bound
+fn higher_ranked(out: &mut String, params: &[GenericParamDef]) {
+    if params.is_empty() {
+        return;
+    }
+    out.push_str("for<");
+    for (idx, param) in params.iter().enumerate() {
+        if idx > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&param.name);
+    }
+    out.push_str("> ");
+}
+
+impl Render for Visibility {
+    fn render(&self, out: &mut String) {
+        match self {
+            Visibility::Public => out.push_str("pub "),
+            Visibility::Default => {}
+            Visibility::Crate => out.push_str("pub(crate) "),
+            Visibility::Restricted { path, .. } => {
+                out.push_str("pub(in ");
+                out.push_str(path);
+                out.push_str(") ");
+            }
+        }
+    }
+}
+
+impl Render for GenericParamDef {
+    fn render(&self, out: &mut String) {
+        match &self.kind {
+            GenericParamDefKind::Lifetime { outlives } => {
+                out.push_str(&self.name);
+                if !outlives.is_empty() {
+                    out.push_str(": ");
+                    out.push_str(&outlives.join(" + "));
+                }
+            }
+            GenericParamDefKind::Type {
+                bounds, default, ..
+            } => {
+                out.push_str(&self.name);
+                if !bounds.is_empty() {
+                    out.push_str(": ");
+                    join(out, bounds, " + ");
+                }
+                if let Some(default) = default {
+                    out.push_str(" = ");
+                    default.render(out);
+                }
+            }
+            GenericParamDefKind::Const { type_, default } => {
+                out.push_str("const ");
+                out.push_str(&self.name);
+                out.push_str(": ");
+                type_.render(out);
+                if let Some(default) = default {
+                    out.push_str(" = ");
+                    out.push_str(default);
+                }
+            }
+        }
+    }
+}
+
+impl Render for WherePredicate {
+    fn render(&self, out: &mut String) {
+        match self {
+            WherePredicate::BoundPredicate {
+                type_,
+                bounds,
+                generic_params,
+            } => {
+                higher_ranked(out, generic_params);
+                type_.render(out);
+                out.push_str(": ");
+                join(out, bounds, " + ");
+            }
+            WherePredicate::LifetimePredicate { lifetime, outlives } => {
+                out.push_str(lifetime);
+                out.push_str(": ");
+                out.push_str(&outlives.join(" + "));
+            }
+            WherePredicate::EqPredicate { lhs, rhs } => {
+                lhs.render(out);
+                out.push_str(" = ");
+                rhs.render(out);
+            }
+        }
+    }
+}
+
+impl Render for Impl {
+    fn render(&self, out: &mut String) {
+        if self.is_unsafe {
+            out.push_str("unsafe ");
+        }
+        out.push_str("impl");
+        generic_params(out, &self.generics);
+        out.push(' ');
+        if let Some(trait_) = &self.trait_ {
+            if self.is_negative {
+                out.push('!');
+            }
+            trait_.render(out);
+            out.push_str(" for ");
+        }
+        self.for_.render(out);
+        where_clause(out, &self.generics);
+    }
+}
@@ -0,0 +1,212 @@
+//! Generates impl blocks for traits with placeholder bodies
+
+use super::signature::{self, Render};
+use rustdoc_types::{GenericBound, GenericParamDefKind, Generics, Item, ItemEnum, Trait};
+use serde::Serialize;
+
+const INDENT: &str = "    ";
+
+/// Traits that `()` implements, so associated types bounded by only these can be `()`
+const UNIT_TRAITS: &[&str] = &[
+    "Any",
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "Eq",
+    "Hash",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "RefUnwindSafe",
+    "Send",
+    "Sized",
+    "Sync",
+    "Unpin",
+    "UnwindSafe",
+];
+
+/// A starting point for implementing a trait
+#[derive(Clone, Debug, Serialize)]
+pub struct ImplSkeleton {
+    /// The impl block with every required item filled in with `todo!()`
+    pub code: String,
+    /// Associated types whose bounds `()` doesn't satisfy, which need a type chosen before the
+    /// impl compiles
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_types: Vec<String>,
+    /// Traits the type must also implement
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub supertraits: Vec<String>,
+    /// Items with a default that can optionally be overridden
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub provided: Vec<String>,
+}
+
+/// Generates an impl of the trait for `for_type`, referring to the trait by `path`
+///
+/// The trait's generic parameters become parameters of the impl so the bounds carry over.
+/// Defaults are dropped since they aren't allowed on impls.
+pub fn generate(
+    krate: &rustdoc_types::Crate,
+    info: &Item,
+    path: &str,
+    for_type: &str,
+) -> Option<ImplSkeleton> {
+    let ItemEnum::Trait(trait_) = &info.inner else {
+        return None;
+    };
+
+    let mut code = header(trait_, path, for_type);
+    let mut assoc = vec![];
+    let mut functions = vec![];
+    let mut provided = vec![];
+    let mut missing_types = vec![];
+
+    for id in &trait_.items {
+        let Some(item) = krate.index.get(id) else {
+            continue;
+        };
+        let Some(name) = item.name.as_deref() else {
+            continue;
+        };
+
+        match &item.inner {
+            ItemEnum::Function(function) if function.has_body => {
+                let mut out = String::new();
+                signature::function_decl(&mut out, name, function);
+                provided.push(out);
+            }
+            ItemEnum::Function(function) => {
+                let mut out = String::new();
+                out.push_str(INDENT);
+                signature::function_decl(&mut out, name, function);
+                out.push_str(" {\n");
+                out.push_str(INDENT);
+                out.push_str(INDENT);
+                out.push_str("todo!()\n");
+                out.push_str(INDENT);
+                out.push('}');
+                functions.push(out);
+            }
+            ItemEnum::AssocType { type_: Some(_), .. }
+            | ItemEnum::AssocConst { value: Some(_), .. } => {
+                provided.extend(signature::item(item));
+            }
+            ItemEnum::AssocType {
+                generics, bounds, ..
+            } => {
+                let mut out = String::new();
+                let is_unit = bounds.iter().all(unit_satisfies);
+                if !is_unit {
+                    out.push_str(INDENT);
+                    out.push_str("// TODO: choose a type that implements ");
+                    signature::join(&mut out, bounds, " + ");
+                    out.push('\n');
+                    missing_types.push(name.to_string());
+                }
+                out.push_str(INDENT);
+                out.push_str("type ");
+                out.push_str(name);
+                signature::generic_params(&mut out, generics);
+                out.push_str(if is_unit { " = ()" } else { " = todo!()" });
+                signature::where_clause(&mut out, generics);
+                out.push(';');
+                assoc.push(out);
+            }
+            ItemEnum::AssocConst { type_, .. } => {
+                let mut out = String::new();
+                out.push_str(INDENT);
+                out.push_str("const ");
+                out.push_str(name);
+                out.push_str(": ");
+                type_.render(&mut out);
+                out.push_str(" = todo!();");
+                assoc.push(out);
+            }
+            _ => continue,
+        }
+    }
+
+    let mut sections = vec![];
+    if !assoc.is_empty() {
+        sections.push(assoc.join("\n"));
+    }
+    sections.extend(functions);
+
+    code.push_str(" {\n");
+    if !sections.is_empty() {
+        code.push_str(&sections.join("\n\n"));
+        code.push('\n');
+    }
+    code.push('}');
+
+    Some(ImplSkeleton {
+        code,
+        missing_types,
+        supertraits: trait_
+            .bounds
+            .iter()
+            .map(|bound| bound.to_source())
+            .collect(),
+        provided,
+    })
+}
+
+/// Returns whether `()` satisfies `bound`, e.g. `Debug` or `'static`
+fn unit_satisfies(bound: &GenericBound) -> bool {
+    match bound {
+        GenericBound::TraitBound { trait_, .. } => {
+            let name = trait_.path.rsplit("::").next().unwrap_or(&trait_.path);
+            // `PartialEq<Rhs>` and the like only hold for `()` with the default argument
+            trait_.args.as_deref().is_none_or(|args| match args {
+                rustdoc_types::GenericArgs::AngleBracketed { args, constraints } => {
+                    args.is_empty() && constraints.is_empty()
+                }
+                _ => false,
+            }) && UNIT_TRAITS.contains(&name)
+        }
+        GenericBound::Outlives(_) => true,
+        GenericBound::Use(_) => true,
+    }
+}
+
+/// Renders `impl<T: Bound> path<T> for Type where ...`
+fn header(trait_: &Trait, path: &str, for_type: &str) -> String {
+    let generics = Generics {
+        params: trait_
+            .generics
+            .params
+            .iter()
+            .cloned()
+            .map(|mut param| {
+                match &mut param.kind {
+                    GenericParamDefKind::Type { default, .. } => *default = None,
+                    GenericParamDefKind::Const { default, .. } => *default = None,
+                    GenericParamDefKind::Lifetime { .. } => {}
+                }
+                param
+            })
+            .collect(),
+        where_predicates: trait_.generics.where_predicates.clone(),
+    };
+
+    let mut out = String::new();
+    if trait_.is_unsafe {
+        out.push_str("unsafe ");
+    }
+    out.push_str("impl");
+    signature::generic_params(&mut out, &generics);
+    out.push(' ');
+    out.push_str(path);
+    if !generics.params.is_empty() {
+        let names: Vec<_> = generics.params.iter().map(|p| p.name.as_str()).collect();
+        out.push('<');
+        out.push_str(&names.join(", "));
+        out.push('>');
+    }
+    out.push_str(" for ");
+    out.push_str(for_type);
+    signature::where_clause(&mut out, &generics);
+    out
+}
@@ -0,0 +1,175 @@
+//! Full-text search over item names and documentation, ranked with BM25
+
+use rustdoc_types::Id;
+use std::collections::HashMap;
+
+/// Controls how quickly repeated terms stop adding to the score
+const K1: f64 = 1.2;
+/// Controls how much longer documents are penalized
+const B: f64 = 0.75;
+/// Terms in an item's name count this many times more than terms in its docs
+const NAME_BOOST: u32 = 3;
+/// The number of characters to show on either side of a match in a snippet
+const SNIPPET_CONTEXT: usize = 80;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "can", "do", "does", "for", "from", "how",
+    "i", "if", "in", "into", "is", "it", "its", "of", "on", "or", "that", "the", "this", "to",
+    "was", "what", "when", "which", "will", "with", "you",
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct DocIndex {
+    /// The item and number of terms for each indexed document
+    documents: Vec<(Id, u32)>,
+    /// The documents containing each term along with the term's frequency
+    postings: HashMap<String, Vec<(usize, u32)>>,
+    /// The number of terms across all documents
+    total_len: u64,
+}
+
+impl DocIndex {
+    pub fn push(&mut self, id: Id, name: &str, docs: Option<&str>) {
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        let mut len = 0;
+
+        for term in tokenize(name) {
+            *frequencies.entry(term).or_default() += NAME_BOOST;
+            len += 1;
+        }
+        for term in docs.into_iter().flat_map(tokenize) {
+            *frequencies.entry(term).or_default() += 1;
+            len += 1;
+        }
+
+        if frequencies.is_empty() {
+            return;
+        }
+
+        let document = self.documents.len();
+        self.documents.push((id, len));
+        for (term, frequency) in frequencies {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push((document, frequency));
+        }
+        self.total_len += len as u64;
+    }
+
+    /// Returns the items matching any of the query terms along with their score
+    ///
+    /// Only items that pass `filter` are scored.
+    pub fn search(&self, query: &str, filter: impl Fn(Id) -> bool) -> Vec<(Id, f64)> {
+        let mut terms: Vec<_> = tokenize(query).collect();
+        terms.sort();
+        terms.dedup();
+
+        let count = self.documents.len() as f64;
+        let average_len = (self.total_len as f64 / count).max(1.0);
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+
+            let matching = postings.len() as f64;
+            let idf = (1.0 + (count - matching + 0.5) / (matching + 0.5)).ln();
+
+            for &(document, frequency) in postings {
+                if !filter(self.documents[document].0) {
+                    continue;
+                }
+
+                let len = self.documents[document].1 as f64;
+                let frequency = frequency as f64;
+                let norm = K1 * (1.0 - B + B * len / average_len);
+                *scores.entry(document).or_default() +=
+                    idf * frequency * (K1 + 1.0) / (frequency + norm);
+            }
+        }
+
+        scores
+            .into_iter()
+            .map(|(document, score)| (self.documents[document].0, score))
+            .collect()
+    }
+}
+
+/// Returns an excerpt of `docs` around the first line that matches the query
+pub fn snippet(docs: &str, query: &str) -> Option<String> {
+    let terms: Vec<_> = tokenize(query).collect();
+
+    let line = docs
+        .lines()
+        .find(|line| tokenize(line).any(|token| terms.contains(&token)))?;
+
+    // center the snippet on the first matching term
+    let lower = line.to_ascii_lowercase();
+    let position = terms
+        .iter()
+        .filter_map(|term| lower.find(term.as_str()))
+        .min()
+        .unwrap_or(0);
+
+    let from = floor_char_boundary(line, position.saturating_sub(SNIPPET_CONTEXT));
+    let to = floor_char_boundary(line, position + SNIPPET_CONTEXT);
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push_str("...");
+    }
+    snippet.push_str(line[from..to].trim());
+    if to < line.len() {
+        snippet.push_str("...");
+    }
+
+    Some(snippet)
+}
+
+/// Splits text into lowercase terms, breaking up `snake_case` and `CamelCase` identifiers
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .flat_map(split_camel_case)
+        .map(|word| stem(&word.to_lowercase()))
+        .filter(|word| word.len() > 1 && !STOP_WORDS.contains(&word.as_str()))
+}
+
+fn split_camel_case(word: &str) -> impl Iterator<Item = &str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut prev_lower = false;
+
+    for (idx, c) in word.char_indices() {
+        if c.is_uppercase() && prev_lower {
+            parts.push(&word[start..idx]);
+            start = idx;
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    parts.push(&word[start..]);
+
+    parts.into_iter().filter(|part| !part.is_empty())
+}
+
+/// Strips plural suffixes so that e.g. `timeouts` matches `timeout`
+fn stem(word: &str) -> String {
+    if word.len() > 4 && word.ends_with("ies") {
+        return format!("{}y", &word[..word.len() - 3]);
+    }
+    if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
+fn floor_char_boundary(text: &str, mut idx: usize) -> usize {
+    if idx >= text.len() {
+        return text.len();
+    }
+    while !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
@@ -0,0 +1,413 @@
+//! Searches functions by the types of their parameters and return value, e.g. `&str -> Result<Url>`
+
+use rustdoc_types::{
+    FunctionSignature, GenericArg, GenericArgs, GenericBound, GenericParamDefKind, Generics, Id,
+    Path, Type, WherePredicate,
+};
+use std::collections::HashMap;
+
+/// Names below this similarity aren't considered a match
+const MIN_NAME_SIMILARITY: f64 = 0.85;
+
+/// A simplified type used for matching, which ignores references, lifetimes and full paths
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeSig {
+    /// A named type, e.g. `Vec<u8>`, `str` or `[u8]` (named `[]`)
+    Named { name: String, args: Vec<TypeSig> },
+    /// A type that matches anything, e.g. an unbounded generic parameter
+    Any,
+}
+
+impl TypeSig {
+    fn named(name: &str, args: Vec<TypeSig>) -> Self {
+        Self::Named {
+            name: name.to_lowercase(),
+            args,
+        }
+    }
+
+    fn unit() -> Self {
+        Self::named("()", vec![])
+    }
+
+    /// Scores how well `candidate` satisfies `self` from 0 (no match) to 1 (exact)
+    fn score(&self, candidate: &TypeSig) -> f64 {
+        let (name, args, candidate_name, candidate_args) = match (self, candidate) {
+            (Self::Any, _) => return 1.0,
+            // generic parameters accept the type, but aren't as specific as a concrete match
+            (_, Self::Any) => return 0.5,
+            (
+                Self::Named { name, args },
+                Self::Named {
+                    name: candidate_name,
+                    args: candidate_args,
+                },
+            ) => (name, args, candidate_name, candidate_args),
+        };
+
+        let similarity = if name == candidate_name {
+            1.0
+        } else {
+            strsim::jaro_winkler(name, candidate_name)
+        };
+
+        if similarity < MIN_NAME_SIMILARITY {
+            // look through wrappers, e.g. `String` partially matches `Option<String>`
+            let nested = candidate_args
+                .iter()
+                .map(|arg| self.score(arg))
+                .fold(0.0, f64::max);
+            return nested * 0.5;
+        }
+
+        // penalize near misses on the name so exact matches come first
+        let similarity = if similarity < 1.0 {
+            similarity * 0.8
+        } else {
+            1.0
+        };
+
+        if args.is_empty() {
+            return similarity;
+        }
+
+        // match the query's generic arguments in order, skipping any extra ones in the candidate
+        let mut total = 0.0;
+        let mut remaining = &candidate_args[..];
+        for arg in args {
+            let best = remaining
+                .iter()
+                .enumerate()
+                .map(|(idx, candidate)| (idx, arg.score(candidate)))
+                .filter(|(_, score)| *score > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((idx, score)) = best {
+                total += score;
+                remaining = &remaining[idx + 1..];
+            }
+        }
+
+        similarity * (0.5 + 0.5 * total / args.len() as f64)
+    }
+}
+
+/// The parameter and return types of a function
+#[derive(Clone, Debug)]
+pub struct FnSig {
+    pub inputs: Vec<TypeSig>,
+    pub output: TypeSig,
+}
+
+impl FnSig {
+    /// Simplifies a function's signature
+    ///
+    /// `self_type` is the name of the type that `Self` refers to in a method.
+    pub fn new(sig: &FunctionSignature, generics: &Generics, self_type: Option<&str>) -> Self {
+        let mut cx = Context {
+            self_type,
+            generics: HashMap::new(),
+        };
+
+        // generic parameters are represented by their first trait bound
+        for param in &generics.params {
+            if let GenericParamDefKind::Type { bounds, .. } = &param.kind {
+                let bound = cx.bounds(bounds);
+                cx.generics.insert(param.name.clone(), bound);
+            }
+        }
+        for predicate in &generics.where_predicates {
+            if let WherePredicate::BoundPredicate {
+                type_: Type::Generic(name),
+                bounds,
+                ..
+            } = predicate
+                && matches!(cx.generics.get(name), None | Some(TypeSig::Any))
+            {
+                let bound = cx.bounds(bounds);
+                cx.generics.insert(name.clone(), bound);
+            }
+        }
+
+        Self {
+            inputs: sig.inputs.iter().map(|(_, ty)| cx.type_(ty)).collect(),
+            output: sig
+                .output
+                .as_ref()
+                .map_or_else(TypeSig::unit, |ty| cx.type_(ty)),
+        }
+    }
+}
+
+struct Context<'a> {
+    self_type: Option<&'a str>,
+    generics: HashMap<String, TypeSig>,
+}
+
+impl Context<'_> {
+    fn type_(&self, ty: &Type) -> TypeSig {
+        match ty {
+            Type::ResolvedPath(path) => self.path(path),
+            Type::DynTrait(dyn_trait) => dyn_trait
+                .traits
+                .first()
+                .map_or(TypeSig::Any, |poly| self.path(&poly.trait_)),
+            Type::ImplTrait(bounds) => self.bounds(bounds),
+            Type::Generic(name) if name == "Self" => match self.self_type {
+                Some(self_type) => TypeSig::named(self_type, vec![]),
+                None => TypeSig::Any,
+            },
+            Type::Generic(name) => self.generics.get(name).cloned().unwrap_or(TypeSig::Any),
+            Type::Primitive(name) => TypeSig::named(name, vec![]),
+            Type::FunctionPointer(_) => TypeSig::named("fn", vec![]),
+            Type::Tuple(types) => {
+                TypeSig::named("()", types.iter().map(|ty| self.type_(ty)).collect())
+            }
+            Type::Slice(ty) | Type::Array { type_: ty, .. } => {
+                TypeSig::named("[]", vec![self.type_(ty)])
+            }
+            Type::Pat { type_, .. } => self.type_(type_),
+            Type::RawPointer { type_, .. } | Type::BorrowedRef { type_, .. } => self.type_(type_),
+            Type::QualifiedPath { name, .. } => TypeSig::named(name, vec![]),
+            Type::Infer => TypeSig::Any,
+        }
+    }
+
+    fn path(&self, path: &Path) -> TypeSig {
+        let name = path.path.rsplit("::").next().unwrap_or(&path.path);
+
+        let args = match path.args.as_deref() {
+            Some(GenericArgs::AngleBracketed { args, .. }) => args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(ty) => Some(self.type_(ty)),
+                    _ => None,
+                })
+                .collect(),
+            Some(GenericArgs::Parenthesized { inputs, output }) => inputs
+                .iter()
+                .chain(output)
+                .map(|ty| self.type_(ty))
+                .collect(),
+            _ => vec![],
+        };
+
+        TypeSig::named(name, args)
+    }
+
+    fn bounds(&self, bounds: &[GenericBound]) -> TypeSig {
+        bounds
+            .iter()
+            .find_map(|bound| match bound {
+                GenericBound::TraitBound { trait_, .. } => Some(self.path(trait_)),
+                _ => None,
+            })
+            .unwrap_or(TypeSig::Any)
+    }
+}
+
+/// A parsed signature query, e.g. `Vec<u8>, usize -> String`
+#[derive(Clone, Debug)]
+pub struct Query {
+    pub inputs: Vec<TypeSig>,
+    /// The return type, if the query has a `->`
+    pub output: Option<TypeSig>,
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Option<Self> {
+        let (inputs, output) = match split_top_level(query, "->").as_slice() {
+            [inputs] => (*inputs, None),
+            [inputs, output] => (*inputs, Some(*output)),
+            _ => return None,
+        };
+
+        let inputs = split_top_level(inputs, ",")
+            .into_iter()
+            .filter(|input| !input.trim().is_empty())
+            .map(parse_type)
+            .collect::<Option<Vec<_>>>()?;
+
+        let output = match output {
+            Some(output) => Some(parse_type(output)?),
+            None => None,
+        };
+
+        if inputs.is_empty() && output.is_none() {
+            return None;
+        }
+
+        Some(Self { inputs, output })
+    }
+
+    /// Scores how well a function matches the query from 0 (no match) to 1 (exact)
+    pub fn score(&self, sig: &FnSig) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0;
+
+        // every query input needs its own parameter
+        let mut used = vec![false; sig.inputs.len()];
+        for input in &self.inputs {
+            let best = sig
+                .inputs
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !used[*idx])
+                .map(|(idx, candidate)| (idx, input.score(candidate)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            let Some((idx, score)) = best.filter(|(_, score)| *score > 0.0) else {
+                return 0.0;
+            };
+            used[idx] = true;
+            total += score;
+            count += 1;
+        }
+
+        if let Some(output) = &self.output {
+            let score = output.score(&sig.output);
+            if score == 0.0 {
+                return 0.0;
+            }
+            total += score;
+            count += 1;
+        }
+
+        // prefer functions that don't need additional arguments
+        let extra = used.iter().filter(|used| !**used).count();
+        total / count as f64 * 0.9f64.powi(extra as i32)
+    }
+}
+
+/// Function signatures for every function in a crate
+#[derive(Clone, Debug, Default)]
+pub struct TypeIndex {
+    functions: Vec<(Id, FnSig)>,
+}
+
+impl TypeIndex {
+    pub fn push(&mut self, id: Id, sig: FnSig) {
+        self.functions.push((id, sig));
+    }
+
+    /// Returns the functions matching `query` along with their score
+    ///
+    /// Only functions that pass `filter` are scored.
+    pub fn search<'a>(
+        &'a self,
+        query: &'a Query,
+        filter: impl Fn(Id) -> bool + 'a,
+    ) -> impl Iterator<Item = (Id, f64)> + 'a {
+        self.functions
+            .iter()
+            .filter(move |(id, _)| filter(*id))
+            .map(|(id, sig)| (*id, query.score(sig)))
+            .filter(|(_, score)| *score > 0.0)
+    }
+}
+
+/// Splits `input` on `separator`, ignoring any separators nested in brackets
+fn split_top_level<'a>(input: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut idx = 0;
+
+    while idx < input.len() {
+        let rest = &input[idx..];
+        if depth == 0 && rest.starts_with(separator) {
+            parts.push(&input[start..idx]);
+            idx += separator.len();
+            start = idx;
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap();
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            // don't count the `>` in `->` as a closing bracket
+            '>' if !input[..idx].ends_with('-') => depth -= 1,
+            ')' | ']' => depth -= 1,
+            _ => {}
+        }
+        idx += c.len_utf8();
+    }
+
+    parts.push(&input[start..]);
+    parts
+}
+
+fn parse_type(input: &str) -> Option<TypeSig> {
+    let mut input = input.trim();
+
+    // references, pointers and lifetimes aren't part of the simplified type
+    loop {
+        let stripped = input
+            .strip_prefix('&')
+            .or_else(|| input.strip_prefix("*const "))
+            .or_else(|| input.strip_prefix("*mut "))
+            .or_else(|| input.strip_prefix("mut "))
+            .or_else(|| input.strip_prefix("dyn "))
+            .or_else(|| input.strip_prefix("impl "));
+        if let Some(rest) = stripped {
+            input = rest.trim_start();
+            continue;
+        }
+        if let Some(rest) = input.strip_prefix('\'') {
+            input = rest
+                .trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+                .trim_start();
+            continue;
+        }
+        break;
+    }
+
+    if input.is_empty() {
+        return None;
+    }
+
+    if input == "_" {
+        return Some(TypeSig::Any);
+    }
+
+    if let Some(inner) = input.strip_prefix('(').and_then(|i| i.strip_suffix(')')) {
+        let args = split_top_level(inner, ",")
+            .into_iter()
+            .filter(|arg| !arg.trim().is_empty())
+            .map(parse_type)
+            .collect::<Option<_>>()?;
+        return Some(TypeSig::named("()", args));
+    }
+
+    if let Some(inner) = input.strip_prefix('[').and_then(|i| i.strip_suffix(']')) {
+        let element = split_top_level(inner, ";").into_iter().next()?;
+        return Some(TypeSig::named("[]", vec![parse_type(element)?]));
+    }
+
+    let (path, args) = match input.find('<') {
+        Some(start) => {
+            let inner = input[start + 1..].strip_suffix('>')?;
+            let args = split_top_level(inner, ",")
+                .into_iter()
+                // skip lifetimes and associated type constraints
+                .filter(|arg| !arg.trim().is_empty() && !arg.trim().starts_with('\''))
+                .filter(|arg| split_top_level(arg, "=").len() == 1)
+                .map(parse_type)
+                .collect::<Option<_>>()?;
+            (&input[..start], args)
+        }
+        None => (input, vec![]),
+    };
+
+    let name = path.trim().rsplit("::").next()?.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    // single letters are treated as generic parameters, like `T`
+    if name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) {
+        return Some(TypeSig::Any);
+    }
+
+    Some(TypeSig::named(name, args))
+}
@@ -1,5 +1,12 @@
-use crate::providers::Providers;
-use cargo_metadata::Metadata;
+use crate::{
+    providers::{
+        Providers,
+        crates_io::enabling_features,
+        rustdoc::{Bump, Crate, Item},
+    },
+    types::{Cursor, Features, ItemKind, SearchFilter, SearchMode},
+};
+use cargo_metadata::{Metadata, Package};
 use rmcp::{
     handler::server::{
         ServerHandler,
@@ -12,7 +19,10 @@ use rmcp::{
     tool, tool_handler, tool_router,
 };
 use serde_json::json;
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
+
+/// The number of entries returned by listing tools when `max_results` isn't provided
+const DEFAULT_PAGE_SIZE: usize = 50;
 
 type McpResult<T = (), E = rmcp::ErrorData> = core::result::Result<T, E>;
 
@@ -33,6 +43,13 @@ struct WorkspaceCrateInfo {
     directory: String,
     #[schemars(description = "The name of the crate")]
     crate_name: String,
+    #[schemars(description = "Max dependencies to return, defaults to 50")]
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -45,6 +62,13 @@ struct CratesIoLatestVersion {
 struct CratesIoVersions {
     #[schemars(description = "The name of the crate")]
     crate_name: String,
+    #[schemars(description = "Max versions to return, defaults to 50")]
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -68,7 +92,251 @@ struct SearchCrate {
     )]
     version: Option<String>,
     #[schemars(description = "Max results to return")]
-    max_results: Option<usize>,
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
+    #[schemars(
+        description = "How to interpret the query: `path` (default) fuzzy matches item paths, `signature` matches functions by types, e.g. `&str -> Result<Url>`, `search_docs` searches the text of the documentation"
+    )]
+    mode: Option<SearchMode>,
+    #[serde(flatten)]
+    filter: SearchFilter,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct WhichFeature {
+    #[schemars(description = "The path of the item, e.g. `tokio::net::TcpStream`")]
+    path: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct SearchWorkspace {
+    #[schemars(description = "The item to search for")]
+    query: String,
+    #[schemars(description = "An absolute path to the directory the crate resides")]
+    directory: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional binary target to search. If not provided, uses the library target."
+    )]
+    bin: Option<String>,
+    #[schemars(description = "Max results to return")]
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
+    #[schemars(
+        description = "How to interpret the query: `path` (default) fuzzy matches item paths, `signature` matches functions by types, e.g. `&str -> Result<Url>`, `search_docs` searches the text of the documentation"
+    )]
+    mode: Option<SearchMode>,
+    #[serde(flatten)]
+    filter: SearchFilter,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ApiDiff {
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(description = "The version to compare from, e.g. `0.7`")]
+    old_version: String,
+    #[schemars(description = "The version to compare to, e.g. `0.8`")]
+    new_version: String,
+    #[serde(default)]
+    #[schemars(description = "Only return breaking changes")]
+    breaking_only: bool,
+    #[schemars(description = "Max changes to return, defaults to 50")]
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct SemverCheck {
+    #[schemars(description = "An absolute path to the directory the crate resides")]
+    directory: String,
+    #[schemars(description = "The name of the workspace crate")]
+    crate_name: String,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct FindImplementors {
+    #[schemars(
+        description = "The path of the trait, e.g. `tower::Service` or `std::error::Error`, or just its name"
+    )]
+    trait_path: String,
+    #[schemars(description = "The name of the crate to search for impls")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[schemars(description = "Max impls to return, defaults to 50")]
+    max_results: Option<NonZeroUsize>,
+    #[schemars(
+        with = "Option<String>",
+        description = "Optional `next_cursor` returned by a previous call to get the next page of results"
+    )]
+    cursor: Option<Cursor>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ImplementedTraits {
+    #[schemars(description = "The path of the type, e.g. `bytes::Bytes`")]
+    path: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ResolveMethods {
+    #[schemars(
+        description = "The type to list methods for, e.g. `my_crate::Wrapper` or `Arc<Mutex<my_crate::Config>>`"
+    )]
+    type_name: String,
+    #[schemars(description = "The name of the crate that defines the type")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct Constructors {
+    #[schemars(description = "The path of the struct or enum, e.g. `url::Url`")]
+    path: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ImplSkeleton {
+    #[schemars(description = "The path of the trait, e.g. `tower::Service`")]
+    trait_path: String,
+    #[schemars(description = "The type to implement the trait for, e.g. `MyService`")]
+    for_type: String,
+    #[schemars(description = "The name of the crate that defines the trait")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct Conversions {
+    #[schemars(
+        description = "The type to list conversions for, e.g. `http::HeaderValue` or `&str`. Types without generic arguments match any instantiation."
+    )]
+    type_name: String,
+    #[schemars(
+        description = "Optional type to convert `type_name` into. If provided, returns the shortest chain of conversions between the types."
+    )]
+    to: Option<String>,
+    #[schemars(description = "The name of the crate whose impls to search")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetItemSource {
+    #[schemars(description = "The path of the item, e.g. `serde_json::from_str`")]
+    path: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version. Ignored for workspace crates."
+    )]
+    version: Option<String>,
+    #[schemars(
+        description = "Optional absolute path to a workspace directory. If provided, the crate is looked up in the workspace instead of crates.io."
+    )]
+    directory: Option<String>,
+    #[schemars(
+        description = "Optional binary target of a workspace crate. If not provided, uses the library target."
+    )]
+    bin: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "Also return the impl blocks of a struct, enum or union")]
+    include_impls: bool,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ListModule {
+    #[schemars(
+        description = "Optional path of the module, e.g. `tokio::sync`. If not provided, lists the crate root."
+    )]
+    path: Option<String>,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version of the crate. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetItemDocs {
+    #[schemars(description = "The path of the item, e.g. `serde::ser::Serialize`")]
+    path: String,
+    #[schemars(description = "The name of the crate")]
+    crate_name: String,
+    #[schemars(
+        description = "Optional version to get documentation for. If not provided, uses latest version."
+    )]
+    version: Option<String>,
+    #[serde(flatten)]
+    features: Features,
 }
 
 #[tool_router]
@@ -112,20 +380,27 @@ impl Server {
     ) -> McpResult<CallToolResult> {
         let directory = params.directory;
         let crate_name = params.crate_name;
+        let max_results = params
+            .max_results
+            .map_or(DEFAULT_PAGE_SIZE, NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
         let meta = self.metadata(&directory)?;
 
-        let packages = meta.workspace_packages();
-        let package = packages.iter().find(|pkg| &*pkg.name == &*crate_name);
-
-        let Some(package) = package else {
-            return Ok(CallToolResult::error(vec![Content::text(format!(
-                "Could not find crate {crate_name:?}"
-            ))]));
+        let package = match workspace_package(&meta, &crate_name) {
+            Ok(package) => package,
+            Err(err) => return Ok(err),
         };
 
-        Ok(CallToolResult::success(vec![
-            Content::json(package).unwrap(),
-        ]))
+        // The dependencies make up most of the package so they're paginated
+        let (dependencies, next_cursor) = cursor.page(&package.dependencies, max_results);
+        let mut info = serde_json::to_value(package).unwrap();
+        info["dependencies"] = json!(dependencies);
+        info["total_dependencies"] = json!(package.dependencies.len());
+        if let Some(next_cursor) = next_cursor {
+            info["next_cursor"] = json!(next_cursor);
+        }
+
+        Ok(CallToolResult::success(vec![Content::json(info).unwrap()]))
     }
 
     #[tool(description = "Returns the latest version for a given crate from crates.io")]
@@ -148,14 +423,22 @@ impl Server {
         }
     }
 
-    #[tool(description = "Returns all available versions for a given crate from crates.io")]
+    #[tool(
+        description = "Returns the available versions for a given crate from crates.io, a page at a time"
+    )]
     async fn crates_io_versions(
         &self,
         Parameters(params): Parameters<CratesIoVersions>,
     ) -> McpResult<CallToolResult> {
         let crate_name = params.crate_name;
+        let max_results = params
+            .max_results
+            .map_or(DEFAULT_PAGE_SIZE, NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
         match self.state.crates_io.fetch_versions(&crate_name).await {
             Ok(versions) => {
+                let total = versions.len();
+                let (versions, next_cursor) = cursor.page(&versions, max_results);
                 let versions: Vec<_> = versions
                     .iter()
                     .map(|v| {
@@ -169,7 +452,9 @@ impl Server {
                 Ok(CallToolResult::success(vec![
                     Content::json(json!({
                         "name": crate_name,
-                        "versions": versions
+                        "versions": versions,
+                        "total": total,
+                        "next_cursor": next_cursor,
                     }))
                     .unwrap(),
                 ]))
@@ -215,29 +500,674 @@ impl Server {
         let crate_name = params.crate_name;
         let version = params.version;
         let query = params.query;
-        let max_results = params.max_results;
+        let max_results = params.max_results.map(NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
+        let mode = params.mode.unwrap_or_default();
+        let filter = params.filter;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+        Ok(search(&krate, mode, &query, &filter, cursor, max_results))
+    }
+
+    #[tool(description = "Search a workspace crate for an item, including private items")]
+    async fn search_workspace(
+        &self,
+        Parameters(params): Parameters<SearchWorkspace>,
+    ) -> McpResult<CallToolResult> {
+        let directory = params.directory;
+        let crate_name = params.crate_name;
+        let bin = params.bin;
+        let query = params.query;
+        let max_results = params.max_results.map(NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
+        let mode = params.mode.unwrap_or_default();
+        let filter = params.filter;
+        let features = params.features;
+        let meta = self.metadata(&directory)?;
+
+        let package = match workspace_package(&meta, &crate_name) {
+            Ok(package) => package,
+            Err(err) => return Ok(err),
+        };
 
         let krate = match self
             .state
             .rustdoc
-            .get_crate_docs(&crate_name, version.as_deref())
+            .get_workspace_docs(package, bin.as_deref(), &features)
             .await
         {
             Ok(krate) => krate,
             Err(err) => {
                 return Ok(CallToolResult::error(vec![Content::text(format!(
-                    "Failed to search for crate {crate_name}: {err}",
+                    "Failed to get documentation for crate {crate_name}: {err}",
                 ))]));
             }
         };
-        let results = krate.search(&query, max_results);
-        Ok(CallToolResult::success(vec![
-            Content::json(results).unwrap(),
-        ]))
+
+        Ok(search(&krate, mode, &query, &filter, cursor, max_results))
+    }
+
+    #[tool(
+        description = "Returns the full documentation for an item in a crate, including fields, variants, methods and implemented traits"
+    )]
+    async fn get_item_docs(
+        &self,
+        Parameters(params): Parameters<GetItemDocs>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let item = match find_item(&krate, &path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        Ok(success(
+            &[&krate],
+            vec![Content::json(krate.item_doc(item)).unwrap()],
+        ))
+    }
+
+    #[tool(
+        description = "Compares the public API of two versions of a crate, listing added, removed, moved and changed items and whether each change is breaking under the Cargo semver guidelines"
+    )]
+    async fn api_diff(&self, Parameters(params): Parameters<ApiDiff>) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let features = params.features;
+        let max_results = params
+            .max_results
+            .map_or(DEFAULT_PAGE_SIZE, NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
+
+        let old = match self
+            .crate_docs(&crate_name, Some(&params.old_version), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+        let new = match self
+            .crate_docs(&crate_name, Some(&params.new_version), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let mut changes = old.api_diff(&new);
+        let breaking = changes.iter().filter(|change| change.breaking).count();
+        if params.breaking_only {
+            changes.retain(|change| change.breaking);
+        }
+        let (page, next_cursor) = cursor.page(&changes, max_results);
+
+        Ok(success(
+            &[&old, &new],
+            vec![
+                Content::json(json!({
+                    "name": crate_name,
+                    "old_version": old.version(),
+                    "new_version": new.version(),
+                    "breaking": breaking,
+                    "total": changes.len(),
+                    "changes": page,
+                    "next_cursor": next_cursor,
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Checks whether the version in a workspace crate's Cargo.toml is bumped enough for the changes to its public API since the latest version published to crates.io"
+    )]
+    async fn semver_check(
+        &self,
+        Parameters(params): Parameters<SemverCheck>,
+    ) -> McpResult<CallToolResult> {
+        let directory = params.directory;
+        let crate_name = params.crate_name;
+        let features = params.features;
+        let meta = self.metadata(&directory)?;
+
+        let package = match workspace_package(&meta, &crate_name) {
+            Ok(package) => package,
+            Err(err) => return Ok(err),
+        };
+
+        let published = match self.state.crates_io.fetch_latest_version(&crate_name).await {
+            Ok(version) => version,
+            Err(err) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Failed to get the latest published version of crate {crate_name}: {err}"
+                ))]));
+            }
+        };
+        let published_version = match published.parse() {
+            Ok(version) => version,
+            Err(err) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Invalid published version {published:?}: {err}"
+                ))]));
+            }
+        };
+
+        let old = match self
+            .crate_docs(&crate_name, Some(&format!("={published}")), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+        let new = match self
+            .state
+            .rustdoc
+            .get_workspace_api(package, &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Failed to get documentation for crate {crate_name}: {err}",
+                ))]));
+            }
+        };
+
+        let changes = old.api_diff(&new);
+        let required = Bump::required(&changes);
+        let actual = Bump::between(&published_version, &package.version);
+        let (breaking, other): (Vec<_>, Vec<_>) =
+            changes.into_iter().partition(|change| change.breaking);
+
+        Ok(success(
+            &[&old],
+            vec![
+                Content::json(json!({
+                    "name": crate_name,
+                    "published_version": published,
+                    "workspace_version": package.version.to_string(),
+                    "required_bump": required,
+                    "actual_bump": actual,
+                    "sufficient": actual >= required,
+                    "suggested_version": required.apply(&published_version).to_string(),
+                    "breaking_changes": breaking,
+                    "other_changes": other,
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Lists the types in a crate that implement a trait, including generic and blanket impls"
+    )]
+    async fn find_implementors(
+        &self,
+        Parameters(params): Parameters<FindImplementors>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let trait_path = params.trait_path;
+        let features = params.features;
+        let max_results = params
+            .max_results
+            .map_or(DEFAULT_PAGE_SIZE, NonZeroUsize::get);
+        let cursor = params.cursor.unwrap_or_default();
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let implementors = krate.implementors(&trait_path);
+        let (page, next_cursor) = cursor.page(&implementors, max_results);
+
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "trait": trait_path,
+                    "total": implementors.len(),
+                    "implementors": page,
+                    "next_cursor": next_cursor,
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Lists every trait a type implements, including auto traits like `Send` and blanket impls"
+    )]
+    async fn implemented_traits(
+        &self,
+        Parameters(params): Parameters<ImplementedTraits>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let item = match find_item(&krate, &path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "path": item.path,
+                    "traits": krate.implemented_traits(item),
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Lists every method that can be called on a value of a type: inherent methods, trait methods and methods reached through `Deref` targets, each with the trait that must be imported. Only types documented in the crate have their methods listed: standard library types like `Arc`, `Box` and `String` are followed to their `Deref` target but their own methods are missing and they're reported as `undocumented`. `Mutex` and `RefCell` don't implement `Deref`, so query the guard type instead, e.g. `MutexGuard<my_crate::Config>`."
+    )]
+    async fn resolve_methods(
+        &self,
+        Parameters(params): Parameters<ResolveMethods>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let type_name = params.type_name;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let methods = krate.methods(&type_name);
+        if methods.methods.is_empty() && methods.undocumented.len() == methods.deref_chain.len() {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "None of {} are documented in crate {crate_name}. Methods of standard library types can't be listed.",
+                methods.deref_chain.join(" -> ")
+            ))]));
+        }
+
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "type": type_name,
+                    "deref_chain": methods.deref_chain,
+                    "methods": methods.methods,
+                    "undocumented": methods.undocumented,
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Lists every way to obtain a value of a struct or enum: associated functions, `Default`, `From`/`TryFrom`/`FromStr` impls, builders and other functions that return it. Results are ranked by simplicity."
+    )]
+    async fn constructors(
+        &self,
+        Parameters(params): Parameters<Constructors>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let item = match find_item(&krate, &path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        if !matches!(
+            item.kind,
+            ItemKind::Struct | ItemKind::Enum | ItemKind::Union
+        ) {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "{} is not a struct, enum or union",
+                item.path
+            ))]));
+        }
+
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "path": item.path,
+                    "constructors": krate.constructors(item),
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Generates an impl block of a trait for a type, with `todo!()` bodies for every required method, associated type and const. Associated types are `()` when that satisfies their bounds, otherwise a `todo!()` placeholder listed in `missing_types`. Provided methods are listed separately as optional overrides."
+    )]
+    async fn impl_skeleton(
+        &self,
+        Parameters(params): Parameters<ImplSkeleton>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let trait_path = params.trait_path;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let item = match find_item(&krate, &trait_path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        match krate.impl_skeleton(item, &params.for_type) {
+            Ok(skeleton) => Ok(success(
+                &[&krate],
+                vec![
+                    Content::json(json!({
+                        "trait": item.path,
+                        "for_type": params.for_type,
+                        "code": skeleton.code,
+                        "missing_types": skeleton.missing_types,
+                        "supertraits": skeleton.supertraits,
+                        "provided": skeleton.provided,
+                    }))
+                    .unwrap(),
+                ],
+            )),
+            Err(err) => Ok(CallToolResult::error(vec![Content::text(err.to_string())])),
+        }
+    }
+
+    #[tool(
+        description = "Lists the `From`, `TryFrom`, `AsRef`, `Borrow`, `FromStr` and `IntoIterator` conversions to and from a type in a crate, or finds the shortest chain of conversions from one type to another"
+    )]
+    async fn conversions(
+        &self,
+        Parameters(params): Parameters<Conversions>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let type_name = params.type_name;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        if let Some(to) = params.to {
+            return match krate.conversion_path(&type_name, &to) {
+                Some(steps) => Ok(success(
+                    &[&krate],
+                    vec![
+                        Content::json(json!({
+                            "from": type_name,
+                            "to": to,
+                            "steps": steps,
+                        }))
+                        .unwrap(),
+                    ],
+                )),
+                None => Ok(CallToolResult::error(vec![Content::text(format!(
+                    "No conversions from {type_name} to {to} found in crate {crate_name}"
+                ))])),
+            };
+        }
+
+        let (from, into) = krate.conversions(&type_name);
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "type": type_name,
+                    "from": from,
+                    "into": into,
+                }))
+                .unwrap(),
+            ],
+        ))
+    }
+
+    #[tool(
+        description = "Returns the source code of an item in a workspace or crates.io crate, e.g. a function body or struct definition"
+    )]
+    async fn get_item_source(
+        &self,
+        Parameters(params): Parameters<GetItemSource>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+        let features = params.features;
+
+        let krate = match &params.directory {
+            Some(directory) => {
+                let meta = self.metadata(directory)?;
+                let package = match workspace_package(&meta, &crate_name) {
+                    Ok(package) => package,
+                    Err(err) => return Ok(err),
+                };
+                match self
+                    .state
+                    .rustdoc
+                    .get_workspace_docs(package, params.bin.as_deref(), &features)
+                    .await
+                {
+                    Ok(krate) => krate,
+                    Err(err) => {
+                        return Ok(CallToolResult::error(vec![Content::text(format!(
+                            "Failed to get documentation for crate {crate_name}: {err}",
+                        ))]));
+                    }
+                }
+            }
+            None => match self
+                .crate_docs(&crate_name, version.as_deref(), &features)
+                .await
+            {
+                Ok(krate) => krate,
+                Err(err) => return Ok(err),
+            },
+        };
+
+        let item = match find_item(&krate, &path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        match krate.item_source(item, params.include_impls) {
+            Ok(sources) => Ok(success(
+                &[&krate],
+                vec![
+                    Content::json(json!({
+                        "path": item.path,
+                        "kind": item.kind,
+                        "sources": sources,
+                    }))
+                    .unwrap(),
+                ],
+            )),
+            Err(err) => Ok(CallToolResult::error(vec![Content::text(err.to_string())])),
+        }
+    }
+
+    #[tool(
+        description = "Lists the submodules and items in a module of a crate, grouped by kind with a summary of each"
+    )]
+    async fn list_module(
+        &self,
+        Parameters(params): Parameters<ListModule>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+        let features = params.features;
+
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let module = match &path {
+            Some(path) => match find_item(&krate, path) {
+                Ok(item) => item,
+                Err(err) => return Ok(err),
+            },
+            None => &krate.items[&krate.root_id],
+        };
+
+        if module.kind != ItemKind::Module {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "{} is not a module",
+                module.path
+            ))]));
+        }
+
+        Ok(success(
+            &[&krate],
+            vec![Content::json(krate.module_doc(module)).unwrap()],
+        ))
+    }
+
+    #[tool(
+        description = "Returns the features that must be enabled in Cargo.toml to use an item from a crate"
+    )]
+    async fn which_feature(
+        &self,
+        Parameters(params): Parameters<WhichFeature>,
+    ) -> McpResult<CallToolResult> {
+        let crate_name = params.crate_name;
+        let version = params.version;
+        let path = params.path;
+
+        // Document everything so items behind features are included
+        let features = Features {
+            all_features: true,
+            ..Default::default()
+        };
+        let krate = match self
+            .crate_docs(&crate_name, version.as_deref(), &features)
+            .await
+        {
+            Ok(krate) => krate,
+            Err(err) => return Ok(err),
+        };
+
+        let item = match find_item(&krate, &path) {
+            Ok(item) => item,
+            Err(err) => return Ok(err),
+        };
+
+        // The feature table is only used to find the features that enable the required ones, so
+        // it's fine if it isn't available
+        let feature_map = self
+            .state
+            .crates_io
+            .fetch_feature_map(&crate_name, version.as_deref())
+            .await
+            .ok();
+
+        let requires_features: Vec<_> = item
+            .requires_features
+            .iter()
+            .map(|feature| {
+                let mut value = json!({ "feature": feature });
+                if let Some(feature_map) = &feature_map {
+                    let enabled_by = enabling_features(feature_map, feature);
+                    value["enabled_by_default"] = json!(enabled_by.iter().any(|f| f == "default"));
+                    value["enabled_by"] = json!(enabled_by);
+                }
+                value
+            })
+            .collect();
+
+        Ok(success(
+            &[&krate],
+            vec![
+                Content::json(json!({
+                    "path": item.path,
+                    "kind": item.kind,
+                    "cfg": item.cfg,
+                    "requires_features": requires_features,
+                }))
+                .unwrap(),
+            ],
+        ))
     }
 }
 
 impl Server {
+    async fn crate_docs(
+        &self,
+        crate_name: &str,
+        version: Option<&str>,
+        features: &Features,
+    ) -> Result<Arc<Crate>, CallToolResult> {
+        self.state
+            .rustdoc
+            .get_crate_docs(crate_name, version, features)
+            .await
+            .map_err(|err| {
+                CallToolResult::error(vec![Content::text(format!(
+                    "Failed to get documentation for crate {crate_name}: {err}",
+                ))])
+            })
+    }
+
     fn metadata(&self, dir: &str) -> McpResult<Metadata> {
         let dir = dir.trim_start_matches("file://");
         eprintln!("Server metadata dir: {dir}");
@@ -251,6 +1181,69 @@ impl Server {
     }
 }
 
+/// Finds an item by path, suggesting similar items if it doesn't exist
+fn find_item<'a>(krate: &'a Crate, path: &str) -> Result<&'a Item, CallToolResult> {
+    krate.get(path).ok_or_else(|| {
+        let suggestions: Vec<_> = krate
+            .search(path, Some(5))
+            .iter()
+            .map(|res| res.path.to_string())
+            .collect();
+        CallToolResult::error(vec![Content::text(format!(
+            "Could not find item {path:?} in crate {}. Similar items: {suggestions:?}",
+            krate.name,
+        ))])
+    })
+}
+
+/// Searches a crate, interpreting the query according to `mode`
+fn search(
+    krate: &Crate,
+    mode: SearchMode,
+    query: &str,
+    filter: &SearchFilter,
+    cursor: Cursor,
+    max_results: Option<usize>,
+) -> CallToolResult {
+    let results = match mode {
+        SearchMode::Path => krate.search_filtered(query, filter, cursor, max_results),
+        SearchMode::Docs => krate.search_docs(query, filter, cursor, max_results),
+        SearchMode::Signature => match krate.search_signature(query, filter, cursor, max_results) {
+            Ok(results) => results,
+            Err(err) => return CallToolResult::error(vec![Content::text(err.to_string())]),
+        },
+    };
+    success(&[krate], vec![Content::json(results).unwrap()])
+}
+
+/// Returns a successful result, followed by the warnings of the crates it was made from
+fn success(krates: &[&Crate], mut content: Vec<Content>) -> CallToolResult {
+    for krate in krates {
+        content.extend(
+            krate
+                .warnings
+                .iter()
+                .map(|warning| Content::text(format!("Warning: {warning}"))),
+        );
+    }
+    CallToolResult::success(content)
+}
+
+/// Finds a workspace member by name
+fn workspace_package<'a>(
+    meta: &'a Metadata,
+    crate_name: &str,
+) -> Result<&'a Package, CallToolResult> {
+    meta.workspace_packages()
+        .into_iter()
+        .find(|pkg| *pkg.name == *crate_name)
+        .ok_or_else(|| {
+            CallToolResult::error(vec![Content::text(format!(
+                "Could not find crate {crate_name:?}"
+            ))])
+        })
+}
+
 #[tool_handler]
 impl ServerHandler for Server {
     fn get_info(&self) -> ServerInfo {
@@ -79,8 +79,10 @@ impl Test {
         let (client, stream) = tokio::io::duplex(1 << 17);
 
         // Create a project with default configuration
-        let mut config = Config::default();
-        config.pwd = ctx.root.clone().into();
+        let config = Config {
+            pwd: ctx.root.clone().into(),
+            ..Default::default()
+        };
         let providers = Providers::new(&config);
         let server = Server::new(providers);
 
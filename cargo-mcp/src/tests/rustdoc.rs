@@ -1,6 +1,55 @@
 use super::TestContext;
-use crate::{providers::rustdoc::RustdocProvider, types::ItemKind};
-use std::sync::Arc;
+use crate::{
+    providers::{
+        metadata::Metadata,
+        rustdoc::{
+            Bump, ConstructorKind, Crate, ImplKind, Match, RustdocProvider, SearchResult,
+            TraitItem, dependency_features,
+        },
+    },
+    types::{ChangeKind, Cursor, Deprecation, Features, ItemKind, SearchFilter, Visibility},
+};
+use std::{path::Path, sync::Arc};
+
+/// Counts the results that exactly matched the query
+fn exact_matches(results: &[SearchResult]) -> usize {
+    results
+        .iter()
+        .filter(|res| res.matched == Some(Match::Exact))
+        .count()
+}
+
+/// Documents a member of the workspace at `root`
+async fn workspace_docs(
+    provider: &RustdocProvider,
+    root: &Path,
+    crate_name: &str,
+    bin: Option<&str>,
+) -> Arc<Crate> {
+    workspace_docs_with_features(provider, root, crate_name, bin, &Features::default()).await
+}
+
+/// Documents a member of the workspace at `root` with the selected features
+async fn workspace_docs_with_features(
+    provider: &RustdocProvider,
+    root: &Path,
+    crate_name: &str,
+    bin: Option<&str>,
+    features: &Features,
+) -> Arc<Crate> {
+    let meta = Metadata::new()
+        .get_metadata(root.to_str().unwrap())
+        .unwrap();
+    let package = meta
+        .workspace_packages()
+        .into_iter()
+        .find(|pkg| *pkg.name == *crate_name)
+        .unwrap();
+    provider
+        .get_workspace_docs(package, bin, features)
+        .await
+        .unwrap()
+}
 
 #[tokio::test]
 async fn test_rustdoc_workspace() {
@@ -46,11 +95,11 @@ impl TestStruct {
     let provider = RustdocProvider::new().unwrap();
 
     // Test rustdoc generation
-    let krate = provider.get_workspace_docs(root).await.unwrap();
+    let krate = workspace_docs(&provider, root, "test-crate", None).await;
 
     // Test root module resolution
     let items = krate.search("test_crate", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
     assert_eq!(&*item.path, "test_crate");
     assert!(matches!(item.kind, ItemKind::Module));
@@ -58,7 +107,7 @@ impl TestStruct {
 
     // Test struct resolution
     let items = krate.search("TestStruct", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
     assert_eq!(&*item.path, "test_crate::TestStruct");
     assert!(matches!(item.kind, ItemKind::Struct));
@@ -80,44 +129,2647 @@ async fn test_rustdoc_crates_io() {
     let provider = RustdocProvider::new().unwrap();
 
     // Test crates.io docs
-    let krate = provider.get_crate_docs("serde", Some("1.0")).await.unwrap();
+    let krate = provider
+        .get_crate_docs("serde", Some("1.0"), &Features::default())
+        .await
+        .unwrap();
 
     // Test root module
     let items = krate.search("serde", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
     assert_eq!(&*item.path, "serde");
     assert!(matches!(item.kind, ItemKind::Module));
 
-    // Test well-known items
+    // Test well-known items, which serde re-exports from serde_core
     let items = krate.search("Serialize", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
-    assert_eq!(&*item.path, "serde::ser::Serialize");
+    assert_eq!(&*item.path, "serde::Serialize");
+    assert!(item.aliases.iter().any(|a| &**a == "serde::ser::Serialize"));
     assert!(matches!(item.kind, ItemKind::Trait));
 
     let items = krate.search("Deserialize", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
-    assert_eq!(&*item.path, "serde::de::Deserialize");
+    assert_eq!(&*item.path, "serde::Deserialize");
+    assert!(
+        item.aliases
+            .iter()
+            .any(|a| &**a == "serde::de::Deserialize")
+    );
     assert!(matches!(item.kind, ItemKind::Trait));
 
     // Test fuzzy matching
-    let items = krate.search("serial", None);
+    let items = krate.search("serial", Some(50));
     eprintln!("Fuzzy search 'serial' found {} items:", items.len());
     assert!(!items.is_empty());
-    assert!(items.iter().any(|i| &*i.path == "serde::ser::Serialize"));
+    assert!(items.iter().any(|i| &*i.path == "serde::Serialize"));
 
-    // Test qualified paths
+    // Test qualified paths through an alias
     let items = krate.search("ser::Serializer", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
-    assert_eq!(&*item.path, "serde::ser::Serializer");
+    assert_eq!(&*item.path, "serde::Serializer");
     assert!(matches!(item.kind, ItemKind::Trait));
 
     let items = krate.search("de::Deserializer", None);
-    assert_eq!(items.len(), 1);
+    assert_eq!(exact_matches(&items), 1);
     let item = &items[0];
-    assert_eq!(&*item.path, "serde::de::Deserializer");
+    assert_eq!(&*item.path, "serde::Deserializer");
     assert!(matches!(item.kind, ItemKind::Trait));
+
+    // Items are reachable by any of their paths
+    let item = krate.get("serde::ser::Serializer").unwrap();
+    assert_eq!(&*item.path, "serde::Serializer");
+
+    // Sources are read from a copy that outlives the vendored crates
+    let sources = krate.item_source(item, false).unwrap();
+    assert!(sources[0].source.contains("trait Serializer"));
+    assert!(
+        std::path::Path::new(&sources[0].file).starts_with(std::env::temp_dir().join("cargo-mcp")),
+        "{}",
+        sources[0].file
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_sources_recopied() {
+    let provider = RustdocProvider::new().unwrap();
+    let features = Features::default();
+    let docs = || provider.get_crate_docs("scopeguard", Some("1.2"), &features);
+
+    let krate = docs().await.unwrap();
+    let item = krate.get("scopeguard::guard").unwrap();
+    let file = krate.item_source(item, false).unwrap()[0].file.clone();
+
+    // the sources are kept in the temporary directory, which can be cleaned up at any time
+    std::fs::remove_file(&file).unwrap();
+
+    let krate = docs().await.unwrap();
+    let item = krate.get("scopeguard::guard").unwrap();
+    let sources = krate.item_source(item, false).unwrap();
+    assert_eq!(sources[0].file, file);
+    assert!(sources[0].source.contains("fn guard"));
+}
+
+#[tokio::test]
+async fn test_rustdoc_reexported_impls() {
+    let provider = RustdocProvider::new().unwrap();
+
+    // rand re-exports some of rand_core's items, but not the `block` module
+    let krate = provider
+        .get_crate_docs("rand", Some("0.8"), &Features::default())
+        .await
+        .unwrap();
+
+    // problems documenting the re-exported crates would be listed here
+    assert!(krate.warnings.is_empty(), "{:?}", krate.warnings);
+    assert!(krate.sources.len() > 1);
+
+    let debug: Vec<_> = krate
+        .implementors("Debug")
+        .iter()
+        .filter_map(|implementor| implementor.impl_.for_path.as_deref().map(String::from))
+        .collect();
+    assert!(debug.iter().any(|path| path == "rand_core::os::OsRng"));
+    assert!(
+        !debug
+            .iter()
+            .any(|path| path.starts_with("rand_core::block::"))
+    );
+
+    // impls of a re-exported trait are kept even when the type isn't re-exported
+    let rng_core: Vec<_> = krate
+        .implementors("rand::RngCore")
+        .iter()
+        .filter_map(|implementor| implementor.impl_.for_path.as_deref().map(String::from))
+        .collect();
+    assert!(
+        rng_core
+            .iter()
+            .any(|path| path == "rand_core::block::BlockRng")
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_item_doc() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+/// A test struct.
+#[derive(Clone)]
+pub struct TestStruct {
+    /// A test field.
+    pub field: Vec<u8>,
+}
+
+impl TestStruct {
+    /// Creates a new instance.
+    pub fn new(field: Vec<u8>) -> Self {
+        Self { field }
+    }
+
+    /// Returns the field length.
+    pub fn len(&self) -> usize {
+        self.field.len()
+    }
+}
+
+/// A test enum.
+pub enum TestEnum {
+    /// A unit variant.
+    Unit,
+    /// A tuple variant.
+    Tuple(&'static str, Option<u32>),
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let item = krate.get("TestStruct").unwrap();
+    let doc = krate.item_doc(item);
+    assert_eq!(doc.path, "test_crate::TestStruct");
+    assert_eq!(doc.docs.as_deref(), Some("A test struct."));
+    assert!(doc.implemented_traits.iter().any(|t| t == "Clone"));
+
+    assert_eq!(doc.fields.len(), 1);
+    assert_eq!(doc.fields[0].name, "field");
+    assert_eq!(doc.fields[0].type_name, "Vec<u8>");
+    assert_eq!(doc.fields[0].docs.as_deref(), Some("A test field."));
+
+    let new = doc.methods.iter().find(|m| m.name == "new").unwrap();
+    assert_eq!(new.docs.as_deref(), Some("Creates a new instance."));
+    assert_eq!(new.args.len(), 1);
+    assert_eq!(new.args[0].name, "field");
+    assert_eq!(new.args[0].type_name, "Vec<u8>");
+    assert_eq!(new.return_type, "Self");
+
+    let len = doc.methods.iter().find(|m| m.name == "len").unwrap();
+    assert_eq!(len.args[0].type_name, "&Self");
+    assert_eq!(len.return_type, "usize");
+
+    let item = krate.get("test_crate::TestEnum").unwrap();
+    let doc = krate.item_doc(item);
+    assert_eq!(doc.variants.len(), 2);
+    assert_eq!(doc.variants[0].name, "Unit");
+    assert!(doc.variants[0].fields.is_empty());
+    assert_eq!(doc.variants[1].name, "Tuple");
+    let types: Vec<_> = doc.variants[1]
+        .fields
+        .iter()
+        .map(|f| f.type_name.as_str())
+        .collect();
+    assert_eq!(types, ["&'static str", "Option<u32>"]);
+}
+
+#[tokio::test]
+async fn test_rustdoc_signatures() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub struct Wrapper<T> {
+    pub inner: T,
+}
+
+impl<T: Clone> Wrapper<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub async fn map<U, F>(self, f: F) -> Wrapper<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Wrapper { inner: f(self.inner) }
+    }
+}
+
+pub trait Visitor<'de>: Sized + Send {
+    fn visit(&self, value: &'de str) -> Option<usize>;
+}
+
+pub struct Limits {
+    pub max_depth: usize,
+    timeout: u64,
+}
+
+pub enum Shape {
+    Circle { radius: f64 },
+}
+
+pub unsafe fn raw(ptr: *const u8, items: &[u16], _callback: impl Fn(u8) -> bool) -> [u8; 4] {
+    unsafe { [*ptr, items.len() as u8, 0, 0] }
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let signature = |path: &str| {
+        let item = krate.get(path).unwrap();
+        item.signature.as_deref().unwrap().to_string()
+    };
+
+    assert_eq!(signature("Wrapper"), "pub struct Wrapper<T>");
+    assert_eq!(signature("Wrapper::new"), "pub fn new(inner: T) -> Self");
+    assert_eq!(
+        signature("Wrapper::get_mut"),
+        "pub fn get_mut(&mut self) -> &mut T"
+    );
+    assert_eq!(
+        signature("Wrapper::map"),
+        "pub async fn map<U, F>(self, f: F) -> Wrapper<U> where F: FnOnce(T) -> U"
+    );
+    assert_eq!(signature("Visitor"), "pub trait Visitor<'de>: Sized + Send");
+    assert_eq!(
+        signature("raw"),
+        "pub unsafe fn raw(ptr: *const u8, items: &[u16], _callback: impl Fn(u8) -> bool) -> [u8; 4]"
+    );
+
+    let doc = krate.item_doc(krate.get("Wrapper").unwrap());
+    assert_eq!(doc.fields[0].signature.as_deref(), Some("pub inner: T"));
+
+    // fields can be found by name
+    assert_eq!(signature("Wrapper::inner"), "pub inner: T");
+    assert_eq!(signature("Shape::Circle::radius"), "radius: f64");
+    let results = krate.search("max_depth", None);
+    assert_eq!(&*results[0].path, "test_crate::Limits::max_depth");
+    assert_eq!(results[0].kind, ItemKind::StructField);
+    assert!(results[0].is_public);
+    assert!(!krate.get("Limits::timeout").unwrap().is_public);
+
+    // the impl's bounds apply to each of its methods
+    assert_eq!(doc.methods.len(), 3);
+    for method in &doc.methods {
+        assert_eq!(
+            method.impl_signature.as_deref(),
+            Some("impl<T: Clone> Wrapper<T>")
+        );
+    }
+    let item = krate.get("Wrapper::new").unwrap();
+    assert_eq!(
+        item.impl_signature.as_deref(),
+        Some("impl<T: Clone> Wrapper<T>")
+    );
+    assert!(krate.get("Wrapper").unwrap().impl_signature.is_none());
+
+    let doc = krate.item_doc(krate.get("Visitor").unwrap());
+    assert_eq!(
+        doc.methods[0].signature.as_deref(),
+        Some("fn visit(&self, value: &'de str) -> Option<usize>")
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_trait_items() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub trait Encoder {
+    /// The output of the encoder.
+    type Output: Clone + Send;
+
+    /// The maximum encoded length.
+    const MAX_LEN: usize = 16;
+
+    /// Encodes a single byte.
+    fn encode_u8(&mut self, value: u8) -> Self::Output;
+
+    /// Encodes a boolean.
+    fn encode_bool(&mut self, value: bool) -> Self::Output {
+        self.encode_u8(value as u8)
+    }
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let encoder = krate.get("Encoder").unwrap();
+    assert_eq!(
+        &*encoder.functions,
+        ["encode_bool".into(), "encode_u8".into()]
+    );
+    assert_eq!(&*encoder.assoc_types, ["Output".into()]);
+    assert_eq!(&*encoder.assoc_consts, ["MAX_LEN".into()]);
+
+    let items = krate.search("Encoder::encode_u8", None);
+    assert_eq!(exact_matches(&items), 1);
+    assert_eq!(&*items[0].path, "test_crate::Encoder::encode_u8");
+    assert_eq!(items[0].trait_item, Some(TraitItem::Required));
+
+    let item = krate.get("Encoder::encode_bool").unwrap();
+    assert_eq!(item.trait_item, Some(TraitItem::Provided));
+
+    let item = krate.get("Encoder::Output").unwrap();
+    assert!(matches!(item.kind, ItemKind::AssocType));
+    assert_eq!(item.trait_item, Some(TraitItem::Required));
+    assert_eq!(&*item.bounds, ["Clone".into(), "Send".into()]);
+    assert_eq!(item.docs.as_deref(), Some("The output of the encoder."));
+
+    let item = krate.get("Encoder::MAX_LEN").unwrap();
+    assert!(matches!(item.kind, ItemKind::AssocConst));
+    assert_eq!(item.trait_item, Some(TraitItem::Provided));
+    assert_eq!(item.value.as_deref(), Some("16"));
+    assert_eq!(item.signature.as_deref(), Some("const MAX_LEN: usize = 16"));
+}
+
+#[tokio::test]
+async fn test_rustdoc_impl_assoc_items() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub struct Config;
+
+impl Config {
+    /// The default port.
+    pub const DEFAULT_PORT: u16 = 8080;
+}
+
+pub enum Mode {
+    Fast,
+}
+
+impl Mode {
+    pub const DEFAULT: Mode = Mode::Fast;
+}
+
+pub union Bits {
+    pub int: u32,
+    pub float: f32,
+}
+
+impl Bits {
+    pub const ZERO: Bits = Bits { int: 0 };
+
+    pub fn to_bits(self) -> u32 {
+        unsafe { self.int }
+    }
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let config = krate.get("Config").unwrap();
+    assert_eq!(&*config.assoc_consts, ["DEFAULT_PORT".into()]);
+    assert!(config.functions.is_empty());
+
+    let item = krate.get("Config::DEFAULT_PORT").unwrap();
+    assert!(matches!(item.kind, ItemKind::AssocConst));
+    assert_eq!(item.value.as_deref(), Some("8080"));
+    assert_eq!(item.docs.as_deref(), Some("The default port."));
+
+    let items = krate.search("Mode::DEFAULT", None);
+    assert_eq!(exact_matches(&items), 1);
+    assert!(matches!(items[0].kind, ItemKind::AssocConst));
+
+    let bits = krate.get("Bits").unwrap();
+    assert_eq!(&*bits.assoc_consts, ["ZERO".into()]);
+    assert_eq!(&*bits.functions, ["to_bits".into()]);
+    assert!(krate.get("Bits::to_bits").is_some());
+}
+
+#[tokio::test]
+async fn test_rustdoc_trait_impl_generics() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::marker::PhantomData;
+
+pub struct Name(String);
+
+impl From<String> for Name {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Iterator for Name {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        None
+    }
+}
+
+pub struct NotSync(PhantomData<*const u8>);
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let name = krate.get("Name").unwrap();
+    let impls: Vec<_> = name.trait_impls.iter().map(|t| &**t).collect();
+    assert!(impls.contains(&"From<String>"), "{impls:?}");
+    assert!(impls.contains(&"From<&str>"), "{impls:?}");
+    assert!(impls.contains(&"Iterator<Item = u8>"), "{impls:?}");
+
+    let not_sync = krate.get("NotSync").unwrap();
+    let impls: Vec<_> = not_sync.trait_impls.iter().map(|t| &**t).collect();
+    assert!(impls.contains(&"!Sync"), "{impls:?}");
+    assert!(impls.contains(&"Unpin"), "{impls:?}");
+}
+
+#[tokio::test]
+async fn test_rustdoc_reexports() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+mod private {
+    /// A re-exported struct.
+    pub struct Hidden;
+}
+
+pub mod public {
+    /// A struct with multiple paths.
+    pub struct Visible;
+}
+
+pub use private::Hidden;
+pub use public::Visible;
+pub use public::Visible as Renamed;
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let items = krate.search("Hidden", None);
+    assert_eq!(exact_matches(&items), 1);
+    assert_eq!(&*items[0].path, "test_crate::Hidden");
+    assert!(items[0].aliases.is_empty());
+
+    let item = krate.get("test_crate::public::Visible").unwrap();
+    assert_eq!(&*item.path, "test_crate::Visible");
+    let aliases: Vec<_> = item.aliases.iter().map(|a| &**a).collect();
+    assert_eq!(
+        aliases,
+        ["test_crate::Renamed", "test_crate::public::Visible"]
+    );
+
+    let item = krate.get("Renamed").unwrap();
+    assert_eq!(&*item.path, "test_crate::Visible");
+}
+
+#[tokio::test]
+async fn test_rustdoc_virtual_workspace() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["crate-a", "crate-b"]
+resolver = "3"
+        "#,
+    );
+
+    ctx.file(
+        "crate-a/Cargo.toml",
+        r#"
+[package]
+name = "crate-a"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+    ctx.file("crate-a/src/lib.rs", "pub struct FromA;");
+
+    ctx.file(
+        "crate-b/Cargo.toml",
+        r#"
+[package]
+name = "crate-b"
+version = "0.1.0"
+edition = "2024"
+
+[[bin]]
+name = "tool-one"
+path = "src/bin/one.rs"
+
+[[bin]]
+name = "tool-two"
+path = "src/bin/two.rs"
+        "#,
+    );
+    ctx.file("crate-b/src/lib.rs", "pub struct FromB;");
+    ctx.file("crate-b/src/bin/one.rs", "struct One;\nfn main() {}");
+    ctx.file("crate-b/src/bin/two.rs", "struct Two;\nfn main() {}");
+
+    let provider = RustdocProvider::new().unwrap();
+
+    let krate = workspace_docs(&provider, ctx.root(), "crate-a", None).await;
+    assert_eq!(&*krate.get("FromA").unwrap().path, "crate_a::FromA");
+
+    let krate = workspace_docs(&provider, ctx.root(), "crate-b", None).await;
+    assert_eq!(&*krate.get("FromB").unwrap().path, "crate_b::FromB");
+
+    let krate = workspace_docs(&provider, ctx.root(), "crate-b", Some("tool-two")).await;
+    assert_eq!(&*krate.get("Two").unwrap().path, "tool_two::Two");
+    assert!(krate.get("One").is_none());
+
+    let meta = Metadata::new()
+        .get_metadata(ctx.root().to_str().unwrap())
+        .unwrap();
+    let package = meta
+        .workspace_packages()
+        .into_iter()
+        .find(|pkg| *pkg.name == *"crate-b")
+        .unwrap();
+    let err = provider
+        .get_workspace_docs(package, Some("missing"), &Features::default())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("tool-one"));
+}
+
+#[tokio::test]
+async fn test_rustdoc_features() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+
+[features]
+default = ["std"]
+std = []
+extra = []
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+#[cfg(feature = "std")]
+pub struct Std;
+
+#[cfg(feature = "extra")]
+pub struct Extra;
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+    assert!(krate.get("Std").is_some());
+    assert!(krate.get("Extra").is_none());
+
+    let features = Features {
+        features: vec!["extra".into()],
+        no_default_features: true,
+        ..Default::default()
+    };
+    let krate =
+        workspace_docs_with_features(&provider, ctx.root(), "test-crate", None, &features).await;
+    assert!(krate.get("Std").is_none());
+    assert!(krate.get("Extra").is_some());
+
+    let features = Features {
+        all_features: true,
+        ..Default::default()
+    };
+    let krate =
+        workspace_docs_with_features(&provider, ctx.root(), "test-crate", None, &features).await;
+    assert!(krate.get("Std").is_some());
+    assert!(krate.get("Extra").is_some());
+}
+
+#[test]
+fn test_dependency_features() {
+    let ctx = TestContext::new().unwrap();
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["parent", "net"]
+resolver = "3"
+        "#,
+    );
+
+    ctx.file(
+        "parent/Cargo.toml",
+        r#"
+[package]
+name = "parent"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+net-impl = { package = "net", path = "../net", optional = true, default-features = false, features = ["base"] }
+
+[features]
+default = ["io"]
+io = ["net-impl/io"]
+full = ["io", "tls"]
+tls = ["net-impl?/tls", "dep:net-impl"]
+        "#,
+    );
+    ctx.file("parent/src/lib.rs", "pub use net_impl::*;");
+
+    ctx.file(
+        "net/Cargo.toml",
+        r#"
+[package]
+name = "net"
+version = "0.1.0"
+edition = "2024"
+
+[features]
+base = []
+io = []
+tls = []
+        "#,
+    );
+    ctx.file("net/src/lib.rs", "");
+
+    let meta = Metadata::new()
+        .get_metadata(ctx.root().to_str().unwrap())
+        .unwrap();
+    let package = meta
+        .workspace_packages()
+        .into_iter()
+        .find(|pkg| *pkg.name == *"parent")
+        .unwrap();
+
+    let features = dependency_features(package, &Features::default(), "net_impl");
+    assert_eq!(features.features, ["base", "io"]);
+    assert!(features.no_default_features);
+    assert!(!features.all_features);
+
+    let selected = Features {
+        features: vec!["full".into()],
+        no_default_features: true,
+        ..Default::default()
+    };
+    let features = dependency_features(package, &selected, "net-impl");
+    assert_eq!(features.features, ["base", "io", "tls"]);
+
+    let selected = Features {
+        no_default_features: true,
+        ..Default::default()
+    };
+    let features = dependency_features(package, &selected, "net_impl");
+    assert_eq!(features.features, ["base"]);
+
+    let selected = Features {
+        all_features: true,
+        ..Default::default()
+    };
+    assert!(dependency_features(package, &selected, "net_impl").all_features);
+
+    let features = dependency_features(package, &Features::default(), "unrelated");
+    assert!(features.features.is_empty() && !features.no_default_features);
+}
+
+#[test]
+fn test_features_cache_key() {
+    let a = Features {
+        features: vec!["b".into(), "a".into(), "b".into()],
+        ..Default::default()
+    };
+    let b = Features {
+        features: vec!["a".into(), "b".into()],
+        ..Default::default()
+    };
+    assert_eq!(a.cache_key(), b.cache_key());
+    assert_ne!(a.cache_key(), Features::default().cache_key());
+
+    let c = Features {
+        no_default_features: true,
+        ..b.clone()
+    };
+    assert_ne!(b.cache_key(), c.cache_key());
+}
+
+#[tokio::test]
+async fn test_rustdoc_required_features() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+
+[features]
+net = []
+tls = []
+fs = []
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub struct Always;
+
+#[cfg(feature = "net")]
+pub mod net {
+    pub struct TcpStream;
+
+    impl TcpStream {
+        #[cfg(feature = "tls")]
+        pub fn upgrade(&self) {}
+    }
+}
+
+#[cfg(all(feature = "fs", unix))]
+pub fn read() {}
+
+#[cfg(any(feature = "net", feature = "fs"))]
+pub fn either() {}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let features = Features {
+        all_features: true,
+        ..Default::default()
+    };
+    let krate =
+        workspace_docs_with_features(&provider, ctx.root(), "test-crate", None, &features).await;
+
+    let item = krate.get("Always").unwrap();
+    assert!(item.requires_features.is_empty());
+    assert!(item.cfg.is_none());
+
+    let item = krate.get("net::TcpStream").unwrap();
+    assert_eq!(item.requires_features, ["net".into()]);
+
+    let item = krate.get("net::TcpStream::upgrade").unwrap();
+    assert_eq!(item.requires_features, ["net".into(), "tls".into()]);
+
+    let item = krate.get("read").unwrap();
+    assert_eq!(item.requires_features, ["fs".into()]);
+    assert_eq!(
+        item.cfg.as_ref().unwrap().to_string(),
+        r#"all(feature = "fs", unix)"#
+    );
+
+    // Either feature works, so neither is strictly required
+    let item = krate.get("either").unwrap();
+    assert!(item.requires_features.is_empty());
+    assert_eq!(
+        item.cfg.as_ref().unwrap().to_string(),
+        r#"any(feature = "net", feature = "fs")"#
+    );
+
+    let doc = krate.item_doc(krate.get("net::TcpStream").unwrap());
+    assert_eq!(doc.requires_features, ["net"]);
+}
+
+#[tokio::test]
+async fn test_rustdoc_signature_search() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub struct Url(String);
+pub struct ParseError;
+
+impl Url {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self(input.to_string()))
+    }
+
+    pub fn join(&self, input: &str) -> Result<Url, ParseError> {
+        Url::parse(&format!("{}/{input}", self.0))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+pub fn to_string(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap()
+}
+
+pub fn from_utf8_lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+pub fn len<T: AsRef<[u8]>>(data: T) -> usize {
+    data.as_ref().len()
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let paths = |query: &str| -> Vec<String> {
+        krate
+            .search_signature(query, &SearchFilter::default(), Cursor::default(), Some(10))
+            .unwrap()
+            .iter()
+            .map(|res| res.path.to_string())
+            .collect()
+    };
+
+    // `Self` resolves to the type and missing generic arguments are fuzzy matched
+    let results = paths("&str -> Result<Url>");
+    assert_eq!(results[0], "test_crate::Url::parse");
+    assert_eq!(results[1], "test_crate::Url::join");
+
+    assert_eq!(paths("Vec<u8> -> String"), ["test_crate::to_string"]);
+    assert_eq!(paths("Url -> &str"), ["test_crate::Url::as_str"]);
+
+    let results = paths("-> String");
+    assert!(results.contains(&"test_crate::to_string".to_string()));
+    assert!(results.contains(&"test_crate::from_utf8_lossy".to_string()));
+
+    // generic parameters match through their bounds
+    assert!(paths("&[u8] -> usize").contains(&"test_crate::len".to_string()));
+
+    assert!(
+        krate
+            .search_signature("->", &SearchFilter::default(), Cursor::default(), None)
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_search_docs() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::time::Duration;
+
+/// Builds a client.
+pub struct ClientBuilder;
+
+impl ClientBuilder {
+    /// Enables a request deadline.
+    ///
+    /// The deadline is applied from when the request starts connecting until the response body
+    /// has finished. Default is no timeout.
+    pub fn timeout(self, _duration: Duration) -> Self {
+        self
+    }
+
+    /// Sets the user agent header.
+    pub fn user_agent(self, _value: &str) -> Self {
+        self
+    }
+}
+
+/// Sends requests to a server.
+pub struct Client;
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let results = krate.search_docs(
+        "how do I set a timeout",
+        &SearchFilter::default(),
+        Cursor::default(),
+        None,
+    );
+    assert_eq!(&*results[0].path, "test_crate::ClientBuilder::timeout");
+    let snippet = results[0].snippet.as_deref().unwrap();
+    assert!(snippet.contains("no timeout"), "{snippet}");
+
+    // terms in names and in the docs both count
+    let results = krate.search_docs(
+        "user agent",
+        &SearchFilter::default(),
+        Cursor::default(),
+        None,
+    );
+    assert_eq!(&*results[0].path, "test_crate::ClientBuilder::user_agent");
+
+    let results = krate.search_docs(
+        "sending requests",
+        &SearchFilter::default(),
+        Cursor::default(),
+        None,
+    );
+    assert!(results.iter().any(|res| &*res.path == "test_crate::Client"));
+
+    assert!(
+        krate
+            .search_docs(
+                "nonexistent",
+                &SearchFilter::default(),
+                Cursor::default(),
+                None
+            )
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_search_filters() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+pub mod sync {
+    pub struct Sender;
+
+    impl Sender {
+        pub fn send(&self) {}
+        fn send_inner(&self) {}
+    }
+
+    pub fn channel() {}
+}
+
+pub trait Send2 {}
+
+pub fn send() {}
+
+mod internal {
+    pub fn send_private() {}
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let paths = |query: &str, filter: &SearchFilter| -> Vec<String> {
+        krate
+            .search_filtered(query, filter, Cursor::default(), Some(20))
+            .iter()
+            .map(|res| res.path.to_string())
+            .collect()
+    };
+
+    let filter = SearchFilter {
+        kinds: vec![ItemKind::Trait],
+        ..Default::default()
+    };
+    assert_eq!(paths("send", &filter), ["test_crate::Send2"]);
+
+    // exact matches outside of the scope don't crowd out the results
+    let filter = SearchFilter {
+        kinds: vec![ItemKind::Function],
+        scope: Some("test_crate::sync".into()),
+        ..Default::default()
+    };
+    let results = paths("send", &filter);
+    assert_eq!(
+        results,
+        [
+            "test_crate::sync::Sender::send",
+            "test_crate::sync::Sender::send_inner"
+        ]
+    );
+
+    let filter = SearchFilter {
+        scope: Some("sync".into()),
+        visibility: Some(Visibility::Private),
+        ..Default::default()
+    };
+    let results = paths("send", &filter);
+    assert!(results.contains(&"test_crate::sync::Sender::send_inner".to_string()));
+    assert!(!results.contains(&"test_crate::sync::Sender::send".to_string()));
+
+    let filter = SearchFilter {
+        kinds: vec![ItemKind::Function],
+        visibility: Some(Visibility::Public),
+        ..Default::default()
+    };
+    let results = paths("send_private", &filter);
+    assert!(!results.contains(&"test_crate::internal::send_private".to_string()));
+
+    let results = krate.search_docs(
+        "sender",
+        &SearchFilter {
+            kinds: vec![ItemKind::Struct],
+            ..Default::default()
+        },
+        Cursor::default(),
+        None,
+    );
+    assert_eq!(results.len(), 1);
+    assert_eq!(&*results[0].path, "test_crate::sync::Sender");
+}
+
+#[tokio::test]
+async fn test_rustdoc_search_ranking() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+macro_rules! types {
+    ($($name:ident),*) => {
+        $(
+            pub struct $name;
+
+            impl $name {
+                pub fn new() -> Self {
+                    Self
+                }
+
+                pub fn new_with(_value: u8) -> Self {
+                    Self
+                }
+            }
+        )*
+    };
+}
+
+types!(A, B, C, D, E, F, G, H);
+
+pub mod net {
+    pub mod tcp {
+        pub struct Stream;
+    }
+}
+
+pub mod parse {}
+
+pub fn parse() {}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    // exact matches are capped rather than returned all at once
+    let results = krate.search("new", Some(3));
+    assert_eq!(results.len(), 3);
+    assert_eq!(exact_matches(&results), 3);
+    // the other constructors, plus prefix and fuzzy matches
+    assert!(results.omitted >= 13, "{}", results.omitted);
+
+    // prefix matches follow the exact ones
+    let results = krate.search("new", Some(10));
+    assert_eq!(exact_matches(&results), 8);
+    assert!(
+        results
+            .iter()
+            .any(|res| res.matched == Some(Match::Prefix) && &*res.name == "new_with")
+    );
+
+    // results are grouped by their parent
+    let parents: Vec<_> = results.iter().map(|res| res.parent()).collect();
+    let mut grouped = parents.clone();
+    grouped.dedup();
+    let mut unique = grouped.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(grouped.len(), unique.len(), "{parents:?}");
+
+    // queries can match the end of a path
+    let results = krate.search("tcp::Stream", None);
+    assert_eq!(&*results[0].path, "test_crate::net::tcp::Stream");
+    assert_eq!(results[0].matched, Some(Match::Suffix));
+
+    // paging through the results returns each of them exactly once
+    let filter = SearchFilter::default();
+    let all = krate.search_filtered("new", &filter, Cursor::default(), Some(100));
+    assert!(all.next_cursor.is_none());
+
+    let mut paged = vec![];
+    let mut cursor = Some(Cursor::default());
+    while let Some(next) = cursor {
+        let page = krate.search_filtered("new", &filter, next, Some(3));
+        assert!(page.len() <= 3);
+        assert_eq!(page.omitted, all.len() - paged.len() - page.len());
+        paged.extend(page.iter().map(|res| res.path.to_string()));
+        cursor = page.next_cursor;
+    }
+    let mut expected: Vec<_> = all.iter().map(|res| res.path.to_string()).collect();
+    expected.sort();
+    paged.sort();
+    assert_eq!(paged, expected);
+    // items that share a path are ordered by kind, so each page holds a different one
+    let kinds: Vec<_> = [Cursor::default(), Cursor::next(1, 2).unwrap()]
+        .into_iter()
+        .map(|cursor| krate.search_filtered("parse", &filter, cursor, Some(1))[0].kind)
+        .collect();
+    assert_eq!(kinds, [ItemKind::Module, ItemKind::Function]);
+}
+
+#[tokio::test]
+async fn test_rustdoc_module_doc() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+//! Test crate documentation.
+
+/// Synchronization primitives.
+///
+/// More details that aren't part of the summary.
+pub mod sync {
+    /// A channel for sending values
+    /// between tasks.
+    pub mod mpsc {
+        pub struct Sender;
+    }
+
+    /// A mutual exclusion lock.
+    pub struct Mutex;
+
+    impl Mutex {
+        pub fn lock(&self) {}
+    }
+
+    pub enum TryLockError {
+        WouldBlock,
+    }
+
+    /// Creates a channel.
+    pub fn channel() {}
+
+    pub use crate::inner::Thing as Renamed;
+}
+
+mod inner {
+    /// Something defined elsewhere.
+    pub struct Thing;
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let root = krate.module_doc(&krate.items[&krate.root_id]);
+    assert_eq!(root.path, "test_crate");
+    assert_eq!(root.summary.as_deref(), Some("Test crate documentation."));
+    let modules: Vec<_> = root.modules.iter().map(|m| m.path.as_str()).collect();
+    assert!(modules.contains(&"test_crate::sync"), "{modules:?}");
+
+    let sync = krate.module_doc(krate.get("test_crate::sync").unwrap());
+    assert_eq!(sync.summary.as_deref(), Some("Synchronization primitives."));
+
+    assert_eq!(sync.modules.len(), 1);
+    assert_eq!(sync.modules[0].path, "test_crate::sync::mpsc");
+    assert_eq!(
+        sync.modules[0].summary.as_deref(),
+        Some("A channel for sending values between tasks.")
+    );
+
+    let kinds: Vec<_> = sync.items.iter().map(|group| group.kind).collect();
+    assert_eq!(
+        kinds,
+        [ItemKind::Struct, ItemKind::Enum, ItemKind::Function]
+    );
+
+    // re-exports are listed under the name they have in the module
+    let structs: Vec<_> = sync.items[0]
+        .items
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(structs, ["Mutex", "Renamed"]);
+    assert_eq!(sync.items[0].items[1].path, "test_crate::sync::Renamed");
+
+    // methods and variants belong to their types rather than the module
+    let functions: Vec<_> = sync.items[2]
+        .items
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(functions, ["channel"]);
+    assert_eq!(sync.items[1].items.len(), 1);
+}
+
+#[tokio::test]
+async fn test_rustdoc_item_source() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["test-crate"]
+resolver = "3"
+        "#,
+    );
+
+    ctx.file(
+        "test-crate/Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "test-crate/src/lib.rs",
+        r#"/// A point.
+#[derive(Clone)]
+pub struct Point {
+    pub x: i32,
+}
+
+impl Point {
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+"#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    // spans are relative to the workspace root rather than the member
+    let item = krate.get("test_crate::Point::new").unwrap();
+    let sources = krate.item_source(item, false).unwrap();
+    assert_eq!(sources.len(), 1);
+    assert!(sources[0].file.ends_with("test-crate/src/lib.rs"));
+    assert_eq!((sources[0].start_line, sources[0].end_line), (8, 10));
+    assert_eq!(
+        sources[0].source,
+        "    pub fn new(x: i32) -> Self {\n        Self { x }\n    }"
+    );
+
+    let item = krate.get("test_crate::Point").unwrap();
+    let doc = krate.item_doc(item);
+    assert!(doc.location.unwrap().ends_with("test-crate/src/lib.rs:3-5"));
+
+    let sources = krate.item_source(item, true).unwrap();
+    assert!(sources[0].source.starts_with("pub struct Point {"));
+    assert!(sources.iter().any(|s| s.source.starts_with("impl Point {")));
+    assert!(
+        sources
+            .iter()
+            .any(|s| s.source.starts_with("impl Default for Point {"))
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_api_diff() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["old", "new"]
+resolver = "3"
+        "#,
+    );
+
+    for dir in ["old", "new"] {
+        ctx.file(
+            &format!("{dir}/Cargo.toml"),
+            &format!(
+                r#"
+[package]
+name = "{dir}"
+version = "0.1.0"
+edition = "2024"
+
+[lib]
+name = "api"
+                "#
+            ),
+        );
+    }
+
+    ctx.file(
+        "old/src/lib.rs",
+        r#"
+pub mod util {
+    pub struct Helper;
+}
+
+pub struct Config {
+    pub name: String,
+}
+
+pub struct Options {
+    pub verbose: bool,
+    level: u8,
+}
+
+pub enum Mode {
+    Fast,
+}
+
+#[non_exhaustive]
+pub enum Open {
+    A,
+}
+
+pub trait Handler {
+    fn handle(&self);
+}
+
+pub fn parse(input: &str) -> u32 {
+    input.len() as u32
+}
+
+pub fn removed() {}
+
+pub fn first<'a, T, U>(items: &'a [T], _other: U) -> Option<&'a T>
+where
+    T: Send,
+    U: Sync,
+{
+    items.first()
+}
+
+pub fn log(args: std::fmt::Arguments<'_>) -> String {
+    args.to_string()
+}
+
+pub mod jobs {
+    pub fn run(id: u32) -> u32 {
+        id
+    }
+}
+
+#[derive(Clone)]
+pub struct Token;
+
+fn private_helper() {}
+        "#,
+    );
+
+    ctx.file(
+        "new/src/lib.rs",
+        r#"
+pub mod util {}
+
+pub mod helpers {
+    pub struct Helper;
+}
+
+pub struct Config {
+    pub name: String,
+    pub port: u16,
+}
+
+pub struct Options {
+    pub verbose: bool,
+    pub color: bool,
+    level: u8,
+}
+
+pub enum Mode {
+    Fast,
+    Slow,
+}
+
+#[non_exhaustive]
+pub enum Open {
+    A,
+    B,
+}
+
+pub trait Handler {
+    fn handle(&self);
+    fn name(&self) -> &str {
+        "handler"
+    }
+    fn flush(&self);
+}
+
+pub fn parse(input: &str, strict: bool) -> u32 {
+    let _ = strict;
+    input.len() as u32
+}
+
+pub fn added() {}
+
+pub fn first<'b, T, U>(items: &'b [T], _other: U) -> Option<&'b T>
+where
+    U: Sync,
+    T: Send,
+{
+    items.first()
+}
+
+use std::fmt;
+
+pub fn log(args: fmt::Arguments<'_>) -> String {
+    args.to_string()
+}
+
+pub mod jobs {}
+
+pub mod tasks {
+    pub fn run(name: &str) -> usize {
+        name.len()
+    }
+}
+
+#[derive(Debug)]
+pub struct Token;
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let old = workspace_docs(&provider, ctx.root(), "old", None).await;
+    let new = workspace_docs(&provider, ctx.root(), "new", None).await;
+
+    let changes = old.api_diff(&new);
+
+    let find = |path: &str, description: &str| {
+        changes
+            .iter()
+            .find(|change| change.path == path && change.description.contains(description))
+            .unwrap_or_else(|| panic!("missing change {path:?} {description:?}"))
+    };
+
+    // breaking changes are listed first
+    let first_non_breaking = changes.iter().position(|c| !c.breaking).unwrap();
+    assert!(changes[first_non_breaking..].iter().all(|c| !c.breaking));
+
+    let change = find("api::util::Helper", "moved");
+    assert_eq!(change.change, ChangeKind::Moved);
+    assert_eq!(change.new.as_deref(), Some("api::helpers::Helper"));
+    assert!(change.breaking);
+    assert!(!changes.iter().any(|c| c.path == "api::helpers::Helper"));
+
+    let change = find("api::parse", "signature changed");
+    assert!(change.breaking);
+    assert!(change.new.as_deref().unwrap().contains("strict: bool"));
+
+    assert!(find("api::removed", "removed").breaking);
+    assert!(!find("api::added", "added").breaking);
+
+    // renamed lifetimes, reordered bounds and shorter paths don't change the signature
+    assert!(!changes.iter().any(|c| c.path == "api::first"));
+    assert!(!changes.iter().any(|c| c.path == "api::log"));
+
+    // an unrelated item with the same name isn't a move
+    assert_eq!(
+        find("api::jobs::run", "removed").change,
+        ChangeKind::Removed
+    );
+    assert!(!find("api::tasks", "added").breaking);
+    assert!(
+        !changes
+            .iter()
+            .any(|c| c.change == ChangeKind::Moved && c.path != "api::util::Helper")
+    );
+
+    assert!(find("api::Config::port", "field added").breaking);
+    assert!(!find("api::Options::color", "added").breaking);
+
+    assert!(find("api::Mode::Slow", "exhaustive enum").breaking);
+    assert!(!find("api::Open::B", "added").breaking);
+
+    assert!(find("api::Handler::flush", "added to a trait").breaking);
+    assert!(!find("api::Handler::name", "added").breaking);
+
+    let change = find("api::Token", "no longer implements");
+    assert!(change.breaking);
+    assert_eq!(change.old.as_deref(), Some("Clone"));
+    assert!(!find("api::Token", "now implements").breaking);
+
+    // unchanged items aren't reported
+    assert!(!changes.iter().any(|c| c.path == "api::Mode::Fast"));
+    assert!(!changes.iter().any(|c| c.path == "api::private_helper"));
+
+    // the public API of a workspace crate leaves out private items
+    assert!(old.get("api::private_helper").is_some());
+    let meta = Metadata::new()
+        .get_metadata(ctx.root().to_str().unwrap())
+        .unwrap();
+    let package = meta
+        .workspace_packages()
+        .into_iter()
+        .find(|pkg| *pkg.name == *"old")
+        .unwrap();
+    let old_api = provider
+        .get_workspace_api(package, &Features::default())
+        .await
+        .unwrap();
+    assert!(old_api.get("api::private_helper").is_none());
+    assert!(old_api.get("api::Token").is_some());
+}
+
+#[test]
+fn test_semver_bump() {
+    let version = |v: &str| semver::Version::parse(v).unwrap();
+
+    assert_eq!(
+        Bump::between(&version("1.2.3"), &version("2.0.0")),
+        Bump::Major
+    );
+    assert_eq!(
+        Bump::between(&version("1.2.3"), &version("1.3.0")),
+        Bump::Minor
+    );
+    assert_eq!(
+        Bump::between(&version("1.2.3"), &version("1.2.4")),
+        Bump::Patch
+    );
+    assert_eq!(
+        Bump::between(&version("1.2.3"), &version("1.2.3")),
+        Bump::None
+    );
+    assert_eq!(
+        Bump::between(&version("0.7.1"), &version("0.8.0")),
+        Bump::Major
+    );
+    assert_eq!(
+        Bump::between(&version("0.7.1"), &version("0.7.2")),
+        Bump::Minor
+    );
+    assert_eq!(
+        Bump::between(&version("0.0.1"), &version("0.0.2")),
+        Bump::Major
+    );
+
+    assert_eq!(Bump::Major.apply(&version("1.2.3")), version("2.0.0"));
+    assert_eq!(Bump::Minor.apply(&version("1.2.3")), version("1.3.0"));
+    assert_eq!(Bump::Patch.apply(&version("1.2.3")), version("1.2.4"));
+    assert_eq!(Bump::Major.apply(&version("0.7.1")), version("0.8.0"));
+    assert_eq!(Bump::Minor.apply(&version("0.7.1")), version("0.7.2"));
+    assert_eq!(Bump::Major.apply(&version("0.0.1")), version("0.0.2"));
+
+    assert_eq!(Bump::required(&[]), Bump::Patch);
+}
+
+#[tokio::test]
+async fn test_rustdoc_trait_impls() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::fmt;
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+pub struct Plain;
+
+impl Describe for Plain {
+    fn describe(&self) -> String {
+        "plain".into()
+    }
+}
+
+pub struct Wrapper<T>(pub T);
+
+impl<T: Describe> Describe for Wrapper<T> {
+    fn describe(&self) -> String {
+        self.0.describe()
+    }
+}
+
+impl<T: Describe> Describe for Vec<T> {
+    fn describe(&self) -> String {
+        String::new()
+    }
+}
+
+pub trait Loud {}
+
+impl<T: Describe> Loud for T {}
+
+#[derive(Debug)]
+pub struct MyError;
+
+impl fmt::Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("error")
+    }
+}
+
+impl std::error::Error for MyError {}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let implementors = krate.implementors("test_crate::Describe");
+    let types: Vec<_> = implementors
+        .iter()
+        .map(|i| (&*i.impl_.for_type, i.impl_.kind))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("Plain", ImplKind::Direct),
+            ("Vec<T>", ImplKind::Generic),
+            ("Wrapper<T>", ImplKind::Generic),
+        ]
+    );
+    assert_eq!(
+        implementors[0].path.map(|p| &**p),
+        Some("test_crate::Plain")
+    );
+    assert!(implementors[1].path.is_none());
+
+    // blanket impls are listed once for the trait
+    let implementors = krate.implementors("Loud");
+    assert!(
+        implementors
+            .iter()
+            .any(|i| i.impl_.kind == ImplKind::Blanket && &*i.impl_.for_type == "T")
+    );
+
+    // traits from the standard library can be found by their `std` path
+    let implementors = krate.implementors("std::error::Error");
+    assert_eq!(implementors.len(), 1);
+    assert_eq!(&*implementors[0].impl_.for_type, "MyError");
+
+    let traits = krate.implemented_traits(krate.get("test_crate::MyError").unwrap());
+    let has =
+        |name: &str, kind: ImplKind| traits.iter().any(|t| &*t.trait_ == name && t.kind == kind);
+    assert!(has("Display", ImplKind::Direct), "{traits:?}");
+    assert!(has("Error", ImplKind::Direct));
+    assert!(has("Debug", ImplKind::Direct));
+    assert!(has("Send", ImplKind::Auto));
+    assert!(has("Unpin", ImplKind::Auto));
+    assert!(
+        traits
+            .iter()
+            .any(|t| t.kind == ImplKind::Blanket && t.trait_.starts_with("ToString"))
+    );
+
+    // blanket impls of local traits apply to local types
+    let traits = krate.implemented_traits(krate.get("test_crate::Plain").unwrap());
+    assert!(traits.iter().any(|t| &*t.trait_ == "Loud"), "{traits:?}");
+}
+
+#[tokio::test]
+async fn test_rustdoc_conversions() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::str::FromStr;
+
+pub struct Token(String);
+
+impl FromStr for Token {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Ok(Self(s.into()))
+    }
+}
+
+pub struct Name(String);
+
+impl From<Token> for Name {
+    fn from(token: Token) -> Self {
+        Self(token.0)
+    }
+}
+
+impl From<String> for Name {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Into<String>> From<Vec<T>> for Name {
+    fn from(value: Vec<T>) -> Self {
+        Self(value.into_iter().map(Into::into).collect())
+    }
+}
+
+pub mod text {
+    pub trait FromStr {
+        fn from_str(s: &str) -> Self;
+    }
+}
+
+pub struct Label;
+
+impl text::FromStr for Label {
+    fn from_str(_: &str) -> Self {
+        Self
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Names(Vec<Name>);
+
+impl IntoIterator for Names {
+    type Item = Name;
+    type IntoIter = std::vec::IntoIter<Name>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let (from, into) = krate.conversions("test_crate::Name");
+    // the parameter is nested in the argument, so the conversion stands for many types
+    let vec = from.iter().find(|c| c.from == "Vec<T>").unwrap();
+    assert!(vec.generic);
+    let from: Vec<_> = from.iter().map(|c| (&*c.from, &*c.trait_)).collect();
+    assert!(from.contains(&("Token", "From<Token>")), "{from:?}");
+    assert!(from.contains(&("String", "From<String>")), "{from:?}");
+    assert_eq!(from.len(), 3, "{from:?}");
+    let into: Vec<_> = into.iter().map(|c| (&*c.to, &*c.call)).collect();
+    assert_eq!(into, [("&str", "value.as_ref()")]);
+
+    let (from, _) = krate.conversions("Token");
+    let parse = from.iter().find(|c| c.from == "&str").unwrap();
+    assert!(parse.fallible);
+    assert_eq!(parse.call, "value.parse::<Token>()");
+
+    // a crate's own `FromStr` can't be used with `str::parse`
+    let (from, _) = krate.conversions("Label");
+    assert!(from.is_empty(), "{from:?}");
+
+    let (_, into) = krate.conversions("Names");
+    assert!(
+        into.iter()
+            .any(|c| c.to == "impl Iterator<Item = Name>" && !c.generic),
+        "{into:?}"
+    );
+
+    let path = krate.conversion_path("&str", "Name").unwrap();
+    let steps: Vec<_> = path.iter().map(|c| (&*c.from, &*c.to)).collect();
+    assert_eq!(steps, [("&str", "Token"), ("Token", "Name")]);
+
+    // `AsRef<str>` followed by parsing
+    let path = krate.conversion_path("Name", "Token").unwrap();
+    assert_eq!(path.len(), 2);
+
+    assert!(krate.conversion_path("Token", "Names").is_none());
+}
+
+#[tokio::test]
+async fn test_rustdoc_impl_skeleton() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::fmt::Debug;
+
+pub trait Handler<Request: Clone, const N: usize = 4>: Send {
+    type Response;
+    type Error: Debug + 'static;
+    type Output<'a>
+    where
+        Self: 'a;
+
+    const NAME: &'static str;
+    const LIMIT: usize = N;
+
+    fn handle(&mut self, request: Request) -> Result<Self::Response, Self::Error>;
+
+    fn view<'a>(&'a self) -> Self::Output<'a>;
+
+    fn ready(&self) -> bool {
+        true
+    }
+}
+
+pub trait Collection {
+    type Item: Clone;
+    type IntoIter: Iterator<Item = Self::Item>;
+    type Key: PartialEq<str>;
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let item = krate.get("test_crate::Handler").unwrap();
+    let skeleton = krate.impl_skeleton(item, "MyHandler").unwrap();
+    let code = &skeleton.code;
+
+    // defaults aren't allowed on impls, but the bounds are kept
+    assert!(
+        code.starts_with(
+            "impl<Request: Clone, const N: usize> test_crate::Handler<Request, N> for MyHandler {"
+        ),
+        "{code}"
+    );
+    assert!(code.contains("    type Response = ();"), "{code}");
+    // `()` satisfies these bounds, so no type needs choosing
+    assert!(code.contains("    type Error = ();"), "{code}");
+    assert!(skeleton.missing_types.is_empty());
+    assert!(
+        code.contains("type Output<'a> = () where Self: 'a;"),
+        "{code}"
+    );
+    assert!(code.contains("const NAME: &'static str = todo!();"));
+    assert!(code.contains(
+        "    fn handle(&mut self, request: Request) -> Result<Self::Response, Self::Error> {\n        todo!()\n    }"
+    ));
+    assert!(code.contains("fn view<'a>(&'a self) -> Self::Output<'a>"));
+    assert!(!code.contains("ready"));
+    assert!(!code.contains("LIMIT"));
+
+    assert_eq!(skeleton.supertraits, ["Send"]);
+    assert_eq!(
+        skeleton.provided,
+        ["const LIMIT: usize = N", "fn ready(&self) -> bool"]
+    );
+
+    // bounds that `()` doesn't satisfy are left for the user to fill in
+    let item = krate.get("test_crate::Collection").unwrap();
+    let skeleton = krate.impl_skeleton(item, "MyCollection").unwrap();
+    let code = &skeleton.code;
+    assert!(code.contains("    type Item = ();"), "{code}");
+    assert!(
+        code.contains(
+            "    // TODO: choose a type that implements Iterator<Item = Self::Item>\n    type IntoIter = todo!();"
+        ),
+        "{code}"
+    );
+    assert!(code.contains("    type Key = todo!();"), "{code}");
+    assert_eq!(skeleton.missing_types, ["IntoIter", "Key"]);
+
+    let item = krate.get("test_crate::Handler::handle").unwrap();
+    assert!(krate.impl_skeleton(item, "MyHandler").is_err());
+}
+
+#[tokio::test]
+async fn test_rustdoc_constructors() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::str::FromStr;
+
+#[derive(Default)]
+pub struct Config {
+    name: String,
+    retries: usize,
+}
+
+impl Config {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            retries: 0,
+        }
+    }
+
+    pub fn load(path: &str, retries: usize) -> std::io::Result<Config> {
+        Ok(Self {
+            name: path.into(),
+            retries,
+        })
+    }
+
+    pub fn with_retries(self, retries: usize) -> Self {
+        Self { retries, ..self }
+    }
+
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+impl From<String> for Config {
+    fn from(name: String) -> Self {
+        Self::new(&name)
+    }
+}
+
+impl FromStr for Config {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Ok(Self::new(s))
+    }
+}
+
+#[derive(Default)]
+pub struct ConfigBuilder {
+    name: String,
+}
+
+impl ConfigBuilder {
+    pub fn build(self) -> Config {
+        Config::new(&self.name)
+    }
+
+    pub fn build_default(&self) -> Config {
+        Config::default()
+    }
+}
+
+pub trait Source {
+    fn config(&self) -> Config;
+}
+
+pub mod text {
+    pub trait FromStr: Sized {
+        fn from_str(s: &str) -> Option<Self>;
+    }
+}
+
+impl text::FromStr for ConfigBuilder {
+    fn from_str(s: &str) -> Option<Self> {
+        Some(Self { name: s.into() })
+    }
+}
+
+pub fn default_config() -> Option<Box<Config>> {
+    None
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let item = krate.get("test_crate::Config").unwrap();
+    let constructors = krate.constructors(item);
+    let found: Vec<_> = constructors
+        .iter()
+        .map(|c| {
+            (
+                c.kind,
+                c.path.as_deref().unwrap_or(&c.signature),
+                c.args,
+                c.fallible,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            (
+                ConstructorKind::Default,
+                "impl Default for Config",
+                0,
+                false
+            ),
+            (
+                ConstructorKind::Builder,
+                "test_crate::ConfigBuilder::build",
+                0,
+                false
+            ),
+            (
+                ConstructorKind::Method,
+                "test_crate::ConfigBuilder::build_default",
+                0,
+                false
+            ),
+            (
+                ConstructorKind::Function,
+                "test_crate::default_config",
+                0,
+                true
+            ),
+            (
+                ConstructorKind::Associated,
+                "test_crate::Config::new",
+                1,
+                false
+            ),
+            (
+                ConstructorKind::Conversion,
+                "impl From<String> for Config",
+                1,
+                false
+            ),
+            (
+                ConstructorKind::Conversion,
+                "impl FromStr for Config",
+                1,
+                true
+            ),
+            (
+                ConstructorKind::Associated,
+                "test_crate::Config::load",
+                2,
+                true
+            ),
+        ]
+    );
+
+    // a crate's own `FromStr` isn't a conversion
+    let item = krate.get("test_crate::ConfigBuilder").unwrap();
+    let constructors = krate.constructors(item);
+    assert!(
+        constructors
+            .iter()
+            .all(|c| c.kind != ConstructorKind::Conversion),
+        "{constructors:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_methods() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+use std::{fmt, ops::Deref};
+
+pub mod inner {
+    pub struct Inner<T>(pub T);
+
+    impl<T> Inner<T> {
+        pub fn get(&self) -> &T {
+            &self.0
+        }
+
+        pub fn value(&self) -> &T {
+            &self.0
+        }
+    }
+
+    pub trait Size {
+        fn size(&self) -> usize;
+    }
+
+    impl<T> Size for Inner<T> {
+        fn size(&self) -> usize {
+            1
+        }
+    }
+
+    impl<T> AsRef<str> for Inner<T> {
+        fn as_ref(&self) -> &str {
+            "inner"
+        }
+    }
+
+    impl<T> AsRef<[u8]> for Inner<T> {
+        fn as_ref(&self) -> &[u8] {
+            b"inner"
+        }
+    }
+}
+
+pub trait Greet {
+    fn greet(&self) -> String;
+
+    fn shout(&self) -> String {
+        self.greet().to_uppercase()
+    }
+}
+
+pub struct Wrapper<T>(inner::Inner<T>);
+
+impl<T> Wrapper<T> {
+    pub fn new(value: T) -> Self {
+        Self(inner::Inner(value))
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0.0
+    }
+
+    pub fn greet(&self) -> &'static str {
+        "hello"
+    }
+
+    fn hidden(&self) {}
+}
+
+impl<T> Deref for Wrapper<T> {
+    type Target = inner::Inner<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Greet for Wrapper<T> {
+    fn greet(&self) -> String {
+        "hi".into()
+    }
+}
+
+impl<T> fmt::Display for Wrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("wrapper")
+    }
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let set = krate.methods("Arc<Wrapper<u8>>");
+    assert_eq!(
+        set.deref_chain,
+        [
+            "Arc<Wrapper<u8>>",
+            "Wrapper<u8>",
+            "test_crate::inner::Inner<u8>"
+        ]
+    );
+    assert_eq!(set.undocumented, ["Arc<Wrapper<u8>>"]);
+
+    let find = |name: &str| {
+        set.methods
+            .iter()
+            .find(|m| &*m.name == name)
+            .unwrap_or_else(|| panic!("{name} missing from {:#?}", set.methods))
+    };
+
+    let get = find("get");
+    assert_eq!((get.self_type.as_str(), get.deref), ("Wrapper<u8>", 1));
+    assert_eq!(get.signature.as_deref(), Some("pub fn get(&self) -> &T"));
+    assert!(get.trait_.is_none());
+    // `Inner::get` is shadowed by `Wrapper::get`
+    assert_eq!(set.methods.iter().filter(|m| &*m.name == "get").count(), 1);
+
+    let value = find("value");
+    assert_eq!(
+        (value.self_type.as_str(), value.deref),
+        ("test_crate::inner::Inner<u8>", 2)
+    );
+
+    let size = find("size");
+    assert_eq!(size.trait_.as_deref(), Some("Size"));
+    assert_eq!(size.import.as_deref(), Some("test_crate::inner::Size"));
+
+    let shout = find("shout");
+    assert_eq!(shout.import.as_deref(), Some("test_crate::Greet"));
+    assert_eq!(
+        shout.signature.as_deref(),
+        Some("fn shout(&self) -> String")
+    );
+
+    let fmt = find("fmt");
+    assert_eq!(fmt.import.as_deref(), Some("std::fmt::Display"));
+
+    let deref = find("deref");
+    assert_eq!(deref.import.as_deref(), Some("std::ops::Deref"));
+
+    // each name is listed once, for the method that method-call syntax picks
+    let count = |name: &str| set.methods.iter().filter(|m| &*m.name == name).count();
+    assert_eq!(count("as_ref"), 1);
+    assert_eq!(find("as_ref").trait_.as_deref(), Some("AsRef"));
+    assert_eq!(count("greet"), 1);
+    assert!(find("greet").trait_.is_none());
+
+    // std wrappers around a crate type reach the type's methods after one deref
+    let set = krate.methods("std::sync::Arc<test_crate::Wrapper<String>>");
+    assert_eq!(set.deref_chain[1], "test_crate::Wrapper<String>");
+    let greet = set.methods.iter().find(|m| &*m.name == "greet").unwrap();
+    assert_eq!(
+        (greet.self_type.as_str(), greet.deref),
+        ("test_crate::Wrapper<String>", 1)
+    );
+
+    // `Mutex` doesn't deref, so nothing past it can be resolved
+    let set = krate.methods("Arc<Mutex<Vec<u8>>>");
+    assert_eq!(set.deref_chain, ["Arc<Mutex<Vec<u8>>>", "Mutex<Vec<u8>>"]);
+    assert_eq!(set.undocumented, set.deref_chain);
+    assert!(set.methods.is_empty());
+
+    let set = krate.methods("MutexGuard<Box<Wrapper<u8>>>");
+    assert_eq!(set.undocumented.len(), 2);
+    assert!(
+        set.methods
+            .iter()
+            .any(|m| &*m.name == "get" && m.deref == 2)
+    );
+
+    // traits in the prelude don't need to be imported
+    let to_string = find("to_string");
+    assert_eq!(to_string.trait_.as_deref(), Some("ToString"));
+    assert!(to_string.import.is_none());
+
+    // associated functions and private methods can't be called on a value
+    assert!(
+        set.methods
+            .iter()
+            .all(|m| &*m.name != "new" && &*m.name != "hidden")
+    );
+}
+
+#[tokio::test]
+async fn test_rustdoc_attributes() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+#[non_exhaustive]
+#[repr(u8)]
+pub enum Level {
+    Low = 1,
+}
+
+#[repr(C)]
+pub struct Point {
+    pub x: i32,
+}
+
+impl Point {
+    #[must_use]
+    pub fn moved(&self) -> Self {
+        Self { x: self.x + 1 }
+    }
+
+    #[deprecated(since = "0.2.0", note = "use `moved` instead")]
+    pub fn shift(&mut self) {}
+}
+
+#[must_use = "the value is the only result"]
+pub fn compute() -> u8 {
+    1
+}
+
+pub unsafe fn compute_unchecked() -> u8 {
+    1
+}
+
+#[deprecated]
+pub fn compute_old() -> u8 {
+    1
+}
+
+pub unsafe trait Marker {}
+
+#[deprecated(note = "use `Point` instead")]
+pub struct OldPoint;
+
+#[allow(deprecated)]
+impl OldPoint {
+    pub fn compute_x(&self) -> i32 {
+        0
+    }
+}
+
+#[deprecated]
+pub enum OldLevel {
+    High,
+}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let attributes = |path: &str| -> Vec<String> {
+        let item = krate.get(path).unwrap();
+        item.attributes.iter().map(|a| a.to_string()).collect()
+    };
+    assert_eq!(
+        attributes("test_crate::Level"),
+        ["non_exhaustive", "repr(u8)"]
+    );
+    assert_eq!(attributes("test_crate::Point"), ["repr(C)"]);
+    assert_eq!(attributes("test_crate::Point::moved"), ["must_use"]);
+    assert_eq!(
+        attributes("test_crate::compute"),
+        [r#"must_use = "the value is the only result""#]
+    );
+    assert_eq!(attributes("test_crate::compute_unchecked"), ["unsafe"]);
+    assert_eq!(attributes("test_crate::Marker"), ["unsafe"]);
+
+    let shift = krate.get("test_crate::Point::shift").unwrap();
+    assert_eq!(
+        shift.deprecated,
+        Some(Deprecation {
+            since: Some("0.2.0".into()),
+            note: Some("use `moved` instead".into()),
+        })
+    );
+    let old = krate.get("test_crate::compute_old").unwrap();
+    assert_eq!(
+        old.deprecated,
+        Some(Deprecation {
+            since: None,
+            note: None
+        })
+    );
+
+    let doc = krate.item_doc(krate.get("test_crate::Point").unwrap());
+    assert_eq!(doc.attributes, ["repr(C)"]);
+    let shift = doc.methods.iter().find(|m| m.name == "shift").unwrap();
+    assert!(shift.deprecated.is_some());
+
+    // members of a deprecated type are deprecated too
+    let old_point = Some(Deprecation {
+        since: None,
+        note: Some("use `Point` instead".into()),
+    });
+    let compute_x = krate.get("test_crate::OldPoint::compute_x").unwrap();
+    assert_eq!(compute_x.deprecated, old_point);
+    let doc = krate.item_doc(krate.get("test_crate::OldPoint").unwrap());
+    assert_eq!(doc.methods[0].deprecated, old_point);
+    let high = krate.get("test_crate::OldLevel::High").unwrap();
+    assert!(high.deprecated.is_some());
+
+    // deprecated items are left out of searches unless asked for
+    let paths = |filter: &SearchFilter| -> Vec<String> {
+        krate
+            .search_filtered("compute", filter, Cursor::default(), None)
+            .iter()
+            .map(|res| res.path.to_string())
+            .collect()
+    };
+    let results = paths(&SearchFilter::default());
+    assert!(results.contains(&"test_crate::compute".to_string()));
+    assert!(!results.contains(&"test_crate::compute_old".to_string()));
+    assert!(!results.contains(&"test_crate::OldPoint::compute_x".to_string()));
+
+    // an exact match that was left out is still reported
+    let results = krate.search_filtered(
+        "compute_old",
+        &SearchFilter::default(),
+        Cursor::default(),
+        None,
+    );
+    assert!(results.iter().all(|res| res.deprecated.is_none()));
+    assert!(results.omitted_deprecated >= 1);
+
+    let results = paths(&SearchFilter {
+        include_deprecated: true,
+        ..Default::default()
+    });
+    assert!(results.contains(&"test_crate::compute_old".to_string()));
+}
+
+#[tokio::test]
+async fn test_rustdoc_stability() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "src/lib.rs",
+        r#"
+#![allow(internal_features)]
+#![feature(staged_api)]
+#![stable(feature = "test_crate", since = "1.0.0")]
+
+#[stable(feature = "test_parse", since = "1.2.0")]
+pub fn parse() {}
+
+#[unstable(feature = "test_parse_fast", issue = "123", reason = "still, being tested")]
+pub fn parse_fast() {}
+
+#[unstable(feature = "test_parse_raw", issue = "none")]
+pub fn parse_raw() {}
+        "#,
+    );
+
+    let provider = RustdocProvider::new().unwrap();
+    let krate = workspace_docs(&provider, ctx.root(), "test-crate", None).await;
+
+    let attributes = |path: &str| -> Vec<String> {
+        let item = krate.get(path).unwrap();
+        item.attributes.iter().map(|a| a.to_string()).collect()
+    };
+    assert_eq!(
+        attributes("test_crate::parse"),
+        [r#"stable(feature = "test_parse", since = "1.2.0")"#]
+    );
+    assert_eq!(
+        attributes("test_crate::parse_fast"),
+        [r#"unstable(feature = "test_parse_fast", issue = "123")"#]
+    );
+    assert_eq!(
+        attributes("test_crate::parse_raw"),
+        [r#"unstable(feature = "test_parse_raw")"#]
+    );
 }
@@ -250,3 +250,226 @@ async fn test_crates_io_features_invalid() {
 
     assert!(result.is_error.unwrap());
 }
+
+#[tokio::test]
+async fn test_search_workspace() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["test-crate"]
+        "#,
+    );
+
+    ctx.file(
+        "test-crate/Cargo.toml",
+        r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2024"
+        "#,
+    );
+
+    ctx.file(
+        "test-crate/src/lib.rs",
+        r#"
+/// A private helper that does nothing.
+fn private_helper() {}
+
+pub fn public_fn() {
+    private_helper()
+}
+        "#,
+    );
+
+    let root = ctx.root().to_string_lossy().to_string();
+    let test = Test::start(ctx).await.unwrap();
+
+    let result = test
+        .call_tool(
+            "search_workspace",
+            vec![
+                ("directory", json!(root)),
+                ("crate_name", json!("test-crate")),
+                ("query", json!("private_helper")),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error.unwrap_or(false));
+    let text = &result.content[0].as_text().unwrap().text;
+    let value: serde_json::Value = serde_json::from_str(text).unwrap();
+    let groups = value["groups"].as_array().unwrap();
+
+    assert_eq!(groups[0]["parent"], "test_crate");
+    let items = groups[0]["results"].as_array().unwrap();
+    assert_eq!(items[0]["item"]["path"], "test_crate::private_helper");
+    assert_eq!(items[0]["item"]["kind"], "function");
+    assert_eq!(items[0]["matched"], "exact");
+
+    // the query is read according to the mode, like in `search_crate`
+    let result = test
+        .call_tool(
+            "search_workspace",
+            vec![
+                ("directory", json!(root)),
+                ("crate_name", json!("test-crate")),
+                ("query", json!("nothing")),
+                ("mode", json!("search_docs")),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error.unwrap_or(false));
+    let text = &result.content[0].as_text().unwrap().text;
+    let value: serde_json::Value = serde_json::from_str(text).unwrap();
+    let items = value["groups"][0]["results"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["item"]["path"], "test_crate::private_helper");
+    assert!(items[0]["snippet"].as_str().unwrap().contains("nothing"));
+
+    let result = test
+        .call_tool(
+            "search_workspace",
+            vec![
+                ("directory", json!(root)),
+                ("crate_name", json!("missing-crate")),
+                ("query", json!("private_helper")),
+            ],
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error.unwrap_or(false));
+}
+
+#[tokio::test]
+async fn test_workspace_crate_info_pagination() {
+    let ctx = Arc::new(TestContext::new().unwrap());
+
+    ctx.file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["app", "dep-a", "dep-b", "dep-c"]
+        "#,
+    );
+
+    ctx.file(
+        "app/Cargo.toml",
+        r#"
+[package]
+name = "app"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+dep-a = { path = "../dep-a" }
+dep-b = { path = "../dep-b" }
+dep-c = { path = "../dep-c" }
+        "#,
+    );
+    ctx.file("app/src/lib.rs", "");
+
+    for dep in ["dep-a", "dep-b", "dep-c"] {
+        ctx.file(
+            &format!("{dep}/Cargo.toml"),
+            &format!(
+                r#"
+[package]
+name = "{dep}"
+version = "0.1.0"
+edition = "2024"
+                "#
+            ),
+        );
+        ctx.file(&format!("{dep}/src/lib.rs"), "");
+    }
+
+    let root = ctx.root().to_string_lossy().to_string();
+    let test = Test::start(ctx).await.unwrap();
+
+    let mut dependencies = vec![];
+    let mut cursor = json!(null);
+    loop {
+        let result = test
+            .call_tool(
+                "workspace_crate_info",
+                vec![
+                    ("directory", json!(root)),
+                    ("crate_name", json!("app")),
+                    ("max_results", json!(2)),
+                    ("cursor", cursor),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let text = &result.content[0].as_text().unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(value["name"], "app");
+        assert_eq!(value["total_dependencies"], 3);
+
+        let page = value["dependencies"].as_array().unwrap();
+        assert!(page.len() <= 2);
+        dependencies.extend(page.iter().map(|dep| dep["name"].clone()));
+
+        cursor = value["next_cursor"].clone();
+        if cursor.is_null() {
+            break;
+        }
+    }
+    assert_eq!(
+        dependencies,
+        [json!("dep-a"), json!("dep-b"), json!("dep-c")]
+    );
+
+    let result = test
+        .call_tool(
+            "workspace_crate_info",
+            vec![
+                ("directory", json!(root)),
+                ("crate_name", json!("app")),
+                ("cursor", json!("not a cursor")),
+            ],
+        )
+        .await;
+    assert!(result.is_err());
+    // empty pages would hand back the same cursor forever
+    let result = test
+        .call_tool(
+            "workspace_crate_info",
+            vec![
+                ("directory", json!(root)),
+                ("crate_name", json!("app")),
+                ("max_results", json!(0)),
+            ],
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_enabling_features() {
+    use crate::providers::crates_io::enabling_features;
+    use std::collections::BTreeMap;
+
+    let features: BTreeMap<String, Vec<String>> = [
+        ("default", vec!["std"]),
+        ("std", vec!["alloc", "dep:libc"]),
+        ("alloc", vec![]),
+        ("full", vec!["net", "serde/std"]),
+        ("net", vec![]),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v.into_iter().map(String::from).collect()))
+    .collect();
+
+    assert_eq!(enabling_features(&features, "alloc"), ["default", "std"]);
+    assert_eq!(enabling_features(&features, "net"), ["full"]);
+    assert!(enabling_features(&features, "full").is_empty());
+}
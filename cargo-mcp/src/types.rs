@@ -1,22 +1,246 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 pub use rustdoc_types::ItemKind;
 
+/// How the query of a search is interpreted
+#[derive(Clone, Copy, Debug, Default, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Fuzzy matches item paths and names
+    #[default]
+    Path,
+    /// Matches functions by parameter and return types, e.g. `&str -> Result<Url>`
+    Signature,
+    /// Full-text search over item names and documentation
+    #[serde(rename = "search_docs")]
+    Docs,
+}
+
+/// Restricts which items a search considers
+#[derive(Clone, Debug, Default, Deserialize, schemars::JsonSchema)]
+pub struct SearchFilter {
+    #[serde(default)]
+    #[schemars(
+        with = "Vec<String>",
+        description = "Optional list of item kinds to include, e.g. `trait`, `function`, `struct`, `enum`, `macro`, `assoc_type`"
+    )]
+    pub kinds: Vec<ItemKind>,
+    #[schemars(
+        description = "Optional path prefix that items must be under, e.g. `tokio::sync` or `sync`"
+    )]
+    pub scope: Option<String>,
+    #[schemars(
+        description = "Optional visibility of items to include. Private items are only available for workspace crates."
+    )]
+    pub visibility: Option<Visibility>,
+    #[serde(default)]
+    #[schemars(description = "Include deprecated items, which are left out by default")]
+    pub include_deprecated: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Items that are reachable from outside of the crate
+    Public,
+    /// Items that are only usable inside of the crate
+    Private,
+}
+
+/// The features to enable when documenting a crate
+#[derive(Clone, Debug, Default, Deserialize, schemars::JsonSchema)]
+pub struct Features {
+    #[serde(default)]
+    #[schemars(description = "Optional list of features to enable")]
+    pub features: Vec<String>,
+    #[serde(default)]
+    #[schemars(description = "Enable all of the crate's features")]
+    pub all_features: bool,
+    #[serde(default)]
+    #[schemars(description = "Disable the crate's default features")]
+    pub no_default_features: bool,
+}
+
+impl Features {
+    /// Returns a key that is the same for any equivalent selection of features
+    pub fn cache_key(&self) -> String {
+        let mut features = self.features.clone();
+        features.sort();
+        features.dedup();
+
+        let mut key = features.join(",");
+        if self.all_features {
+            key.push_str(";all-features");
+        }
+        if self.no_default_features {
+            key.push_str(";no-default-features");
+        }
+        key
+    }
+}
+
+/// An opaque position in a paginated list of results
+///
+/// Tools return a `next_cursor` when there are more results, which can be passed back as the
+/// `cursor` parameter to get the following page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cursor {
+    offset: usize,
+}
+
+impl Cursor {
+    /// The number of results before the page
+    pub fn offset(self) -> usize {
+        self.offset
+    }
+
+    /// Returns the page of `items` starting at the cursor along with the cursor for the next page
+    pub fn page<T>(self, items: &[T], max_results: usize) -> (&[T], Option<Self>) {
+        let start = self.offset.min(items.len());
+        let end = start.saturating_add(max_results).min(items.len());
+        (&items[start..end], Self::next(end, items.len()))
+    }
+
+    /// Returns the cursor for the page starting at `offset` if there are any results left
+    pub fn next(offset: usize, total: usize) -> Option<Self> {
+        (offset < total).then_some(Self { offset })
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "c{:x}", self.offset)
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cursor = String::deserialize(deserializer)?;
+        cursor
+            .strip_prefix('c')
+            .and_then(|offset| usize::from_str_radix(offset, 16).ok())
+            .map(|offset| Self { offset })
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid cursor {cursor:?}")))
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct ItemDoc {
     pub name: String,
+    pub path: String,
     pub kind: ItemKind,
     pub docs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// The header of the inherent impl block the item is defined in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impl_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires_features: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    /// Attributes that affect how the item can be used, e.g. `must_use` or `non_exhaustive`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
+    /// The file and lines the item is defined at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub implemented_traits: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub methods: Vec<MethodDoc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<FieldDoc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub variants: Vec<VariantDoc>,
 }
 
+/// The `#[deprecated]` notice of an item
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Deprecation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A change to the public API of a crate between two versions
+#[derive(Debug, Serialize)]
+pub struct ApiChange {
+    pub path: String,
+    pub kind: ItemKind,
+    pub change: ChangeKind,
+    /// Whether the change requires a major version bump under the Cargo semver guidelines
+    pub breaking: bool,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    /// The item is only reachable at a new path
+    Moved,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SourceDoc {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleDoc {
+    pub path: String,
+    pub summary: Option<String>,
+    /// Submodules, which can be listed in turn to walk the crate
+    pub modules: Vec<ChildDoc>,
+    /// The other children of the module, grouped by kind
+    pub items: Vec<ChildGroup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChildGroup {
+    pub kind: ItemKind,
+    pub items: Vec<ChildDoc>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ChildDoc {
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MethodDoc {
     pub name: String,
+    pub signature: Option<String>,
     pub docs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    /// The header of the inherent impl block the method is defined in, e.g.
+    /// `impl<T: Serialize> Wrapper<T>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impl_signature: Option<String>,
     pub args: Vec<ArgDoc>,
     pub return_type: String,
 }
@@ -25,6 +249,7 @@ pub struct MethodDoc {
 pub struct ArgDoc {
     pub name: String,
     pub type_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub docs: Option<String>,
 }
 
@@ -32,6 +257,7 @@ pub struct ArgDoc {
 pub struct FieldDoc {
     pub name: String,
     pub type_name: String,
+    pub signature: Option<String>,
     pub docs: Option<String>,
 }
 
@@ -39,5 +265,6 @@ pub struct FieldDoc {
 pub struct VariantDoc {
     pub name: String,
     pub docs: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<FieldDoc>,
 }